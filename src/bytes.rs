@@ -0,0 +1,178 @@
+use core::fmt;
+use core::mem::size_of;
+use core::ops::Deref;
+
+use crate::{BitOrder, Error, Reader, Uint, Writer};
+
+/// Byte order of serialized words.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    #[default]
+    Big,
+    Little,
+}
+
+/// The bytes of a finished word that hold written bits.
+#[derive(Clone, Copy)]
+pub struct WordBytes<T: Uint> {
+    bytes: T::Bytes,
+    start: usize,
+    end: usize,
+}
+
+impl<T: Uint> Deref for WordBytes<T> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        &self.bytes.as_ref()[self.start..self.end]
+    }
+}
+
+impl<T: Uint> AsRef<[u8]> for WordBytes<T> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<T: Uint> fmt::Debug for WordBytes<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: Uint> PartialEq for WordBytes<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Uint> Eq for WordBytes<T> {}
+
+impl<T: Uint, O: BitOrder> Writer<T, O> {
+    /// Finishes the word and returns its `ceil(bits_written / 8)` low-order
+    /// bytes in the given byte order.
+    pub fn finish_bytes(self, endian: Endian) -> WordBytes<T> {
+        let len = usize::from(self.len.div_ceil(8));
+        let size = size_of::<T>();
+
+        match endian {
+            Endian::Big => WordBytes {
+                bytes: self.bit_vec.to_be_bytes(),
+                start: size - len,
+                end: size,
+            },
+            Endian::Little => WordBytes {
+                bytes: self.bit_vec.to_le_bytes(),
+                start: 0,
+                end: len,
+            },
+        }
+    }
+}
+
+impl<T: Uint, O: BitOrder> Reader<T, O> {
+    /// Reads the first `len` bits of a word serialized with
+    /// `Writer::finish_bytes`. `bytes` must be exactly `ceil(len / 8)` long.
+    #[track_caller]
+    pub fn from_bytes(bytes: &[u8], endian: Endian, len: u16) -> Self {
+        crate::error::unwrap(Self::try_from_bytes(bytes, endian, len))
+    }
+
+    pub fn try_from_bytes(bytes: &[u8], endian: Endian, len: u16) -> Result<Self, Error> {
+        let reader = Self::try_with_len(T::MIN, len)?;
+        let expected = usize::from(len.div_ceil(8));
+
+        if bytes.len() != expected {
+            return Err(Error::InvalidByteLength {
+                len: bytes.len(),
+                expected,
+            });
+        }
+
+        let mut buf = T::MIN.to_be_bytes();
+        let size = size_of::<T>();
+
+        let bit_vec = match endian {
+            Endian::Big => {
+                buf.as_mut()[size - expected..].copy_from_slice(bytes);
+                T::from_be_bytes(buf)
+            }
+            Endian::Little => {
+                buf.as_mut()[..expected].copy_from_slice(bytes);
+                T::from_le_bytes(buf)
+            }
+        };
+
+        Ok(Reader { bit_vec, ..reader })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Limbs, Lsb0};
+
+    #[test]
+    fn minimal_bytes() {
+        let w = Writer::<u64>::default().write(4, 0xau8).write(8, 0xbcu8);
+
+        assert_eq!(*w.finish_bytes(Endian::Big), [0x0a, 0xbc]);
+
+        let w = Writer::<u64>::default().write(4, 0xau8).write(8, 0xbcu8);
+
+        assert_eq!(*w.finish_bytes(Endian::Little), [0xbc, 0x0a]);
+        assert!(Writer::<u32>::new().finish_bytes(Endian::Big).is_empty());
+
+        let w = Writer::<u32>::new().write(32, u32::MAX);
+
+        assert_eq!(w.finish_bytes(Endian::Little).len(), 4);
+    }
+
+    #[test]
+    fn round_trip() {
+        for endian in [Endian::Big, Endian::Little] {
+            let bytes = Writer::<u128, _>::with_order(Lsb0)
+                .write(3, 5u8)
+                .write(17, 0x1_2345u32)
+                .finish_bytes(endian);
+
+            assert_eq!(bytes.len(), 3);
+
+            let mut r = Reader::<u128, Lsb0>::from_bytes(&bytes, endian, 20);
+
+            assert_eq!(r.read_next(3), 5);
+            assert_eq!(r.read_next(17), 0x1_2345);
+            assert!(r.is_exhausted());
+
+            let bytes = Writer::<Limbs<3>>::default()
+                .write(100, u128::MAX >> 28)
+                .write(30, 0x2bad_cafe_u32)
+                .finish_bytes(endian);
+
+            assert_eq!(bytes.len(), 17);
+
+            let mut r = Reader::<Limbs<3>>::from_bytes(&bytes, endian, 130);
+
+            assert_eq!(r.read_as::<u128>(100), u128::MAX >> 28);
+            assert_eq!(r.read_as::<u32>(30), 0x2bad_cafe);
+        }
+    }
+
+    #[test]
+    fn invalid_length() {
+        assert_eq!(
+            Reader::<u32>::try_from_bytes(&[1, 2], Endian::Big, 20).err(),
+            Some(Error::InvalidByteLength {
+                len: 2,
+                expected: 3
+            })
+        );
+        assert_eq!(
+            Reader::<u16>::try_from_bytes(&[1, 2, 3], Endian::Big, 24).err(),
+            Some(Error::InvalidPosition { pos: 24, size: 16 })
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! `const fn` counterparts of `Writer::write` and `Reader::read_next` for the
+//! primitive words, usable in `const` items. They panic where the runtime
+//! versions panic, which turns into a compile error in const context.
+
+use crate::{Lsb0, Msb0, Reader, Writer};
+
+macro_rules! mask {
+    ( $Ty:ty, $count:expr ) => {
+        if $count == 0 {
+            0
+        } else {
+            <$Ty>::MAX >> (<$Ty>::BITS - $count as u32)
+        }
+    };
+}
+
+macro_rules! impl_const {
+    ( $($Ty:ty),+ ) => {
+        $(
+            impl Writer<$Ty, Msb0> {
+                pub const fn write_const(mut self, count: u8, src: $Ty) -> Self {
+                    assert!(count as u16 <= self.remaining(), "field exceeds the word");
+
+                    let bits = src & mask!($Ty, count);
+
+                    self.bit_vec = if count as u32 == <$Ty>::BITS {
+                        bits
+                    } else {
+                        self.bit_vec << count | bits
+                    };
+                    self.len += count as u16;
+
+                    self
+                }
+            }
+
+            impl Writer<$Ty, Lsb0> {
+                pub const fn write_const(mut self, count: u8, src: $Ty) -> Self {
+                    assert!(count as u16 <= self.remaining(), "field exceeds the word");
+
+                    if count > 0 {
+                        self.bit_vec |= (src & mask!($Ty, count)) << self.len;
+                        self.len += count as u16;
+                    }
+
+                    self
+                }
+            }
+
+            impl Reader<$Ty, Msb0> {
+                pub const fn read_const(&mut self, count: u8) -> $Ty {
+                    assert!(count as u16 <= self.remaining(), "field exceeds the word");
+
+                    if count == 0 {
+                        return 0;
+                    }
+
+                    let shift = self.len - count as u16 - self.pos;
+
+                    self.pos += count as u16;
+
+                    (self.bit_vec >> shift) & mask!($Ty, count)
+                }
+            }
+
+            impl Reader<$Ty, Lsb0> {
+                pub const fn read_const(&mut self, count: u8) -> $Ty {
+                    assert!(count as u16 <= self.remaining(), "field exceeds the word");
+
+                    if count == 0 {
+                        return 0;
+                    }
+
+                    let shift = self.pos;
+
+                    self.pos += count as u16;
+
+                    (self.bit_vec >> shift) & mask!($Ty, count)
+                }
+            }
+        )+
+    };
+}
+
+impl_const!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HDR: u32 = Writer::<u32>::new()
+        .write_const(4, 0xa)
+        .write_const(12, 0x1234)
+        .write_const(0, 1)
+        .write_const(16, 0xbeef)
+        .finish();
+
+    const HDR_LSB: u16 = Writer::<u16, _>::with_order(Lsb0)
+        .write_const(3, 5)
+        .write_const(13, 0x1fff)
+        .finish();
+
+    const FIELDS: (u32, u32, u32) = {
+        let mut r = Reader::<u32>::new(HDR);
+
+        (r.read_const(4), r.read_const(12), r.read_const(16))
+    };
+
+    #[test]
+    fn matches_runtime() {
+        let hdr = Writer::<u32>::default()
+            .write(4, 0xau8)
+            .write(12, 0x1234u16)
+            .write(0, 1u8)
+            .write(16, 0xbeefu16)
+            .finish();
+
+        assert_eq!(HDR, hdr);
+        assert_eq!(FIELDS, (0xa, 0x234, 0xbeef));
+
+        let hdr = Writer::<u16, _>::with_order(Lsb0)
+            .write(3, 5u8)
+            .write(13, 0x1fffu16)
+            .finish();
+
+        assert_eq!(HDR_LSB, hdr);
+
+        let mut r = Reader::<u16, _>::with_order(HDR_LSB, Lsb0);
+
+        assert_eq!(r.read_const(3), 5);
+        assert_eq!(r.read_const(13), 0x1fff);
+    }
+
+    #[test]
+    fn full_width() {
+        const W: u128 = Writer::<u128>::new().write_const(128, u128::MAX).finish();
+
+        assert_eq!(W, u128::MAX);
+        assert_eq!(Reader::<u128>::new(W).read_const(128), u128::MAX);
+        assert_eq!(Reader::<u8, _>::with_order(0x80, Lsb0).read_const(8), 0x80);
+    }
+
+    #[test]
+    #[should_panic(expected = "field exceeds the word")]
+    fn overflow_panics() {
+        Writer::<u8>::new().write_const(5, 0).write_const(4, 0);
+    }
+}
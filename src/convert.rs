@@ -0,0 +1,96 @@
+use crate::{bit_size, error, BitOrder, Error, Reader, Uint};
+
+pub trait FromBits<T: Uint>: Sized {
+    const BITS: u16;
+
+    /// Converts a field of at most `Self::BITS` bits, right-aligned in `bits`.
+    fn from_bits(bits: T) -> Self;
+}
+
+impl<T: Uint, U: Uint + TryFrom<T>> FromBits<T> for U {
+    const BITS: u16 = bit_size::<U>();
+
+    #[inline]
+    fn from_bits(bits: T) -> Self {
+        cast(bits)
+    }
+}
+
+impl<T: Uint> FromBits<T> for bool {
+    const BITS: u16 = 1;
+
+    #[inline]
+    fn from_bits(bits: T) -> Self {
+        bits != T::MIN
+    }
+}
+
+#[inline]
+pub(crate) fn cast<A, B: TryFrom<A>>(src: A) -> B {
+    match B::try_from(src) {
+        Ok(dst) => dst,
+        Err(_) => unreachable!("field bits fit both types"),
+    }
+}
+
+impl<T: Uint, O: BitOrder> Reader<T, O> {
+    #[track_caller]
+    pub fn read_as<U: FromBits<T>>(&mut self, count: u8) -> U {
+        error::unwrap(self.try_read_as(count))
+    }
+
+    pub fn try_read_as<U: FromBits<T>>(&mut self, count: u8) -> Result<U, Error> {
+        if u16::from(count) > U::BITS {
+            return Err(Error::InvalidWidth {
+                width: count,
+                size: U::BITS,
+            });
+        }
+
+        self.try_read_next(count).map(U::from_bits)
+    }
+
+    #[track_caller]
+    pub fn read_fixed<U: FromBits<T>, const N: u8>(&mut self) -> U {
+        error::unwrap(self.try_read_fixed::<U, N>())
+    }
+
+    pub fn try_read_fixed<U: FromBits<T>, const N: u8>(&mut self) -> Result<U, Error> {
+        const {
+            assert!(N as u16 <= U::BITS, "field is wider than the target type");
+            assert!(N as u16 <= bit_size::<T>(), "field is wider than the word");
+        }
+
+        self.try_read_next(N).map(U::from_bits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Writer;
+
+    #[test]
+    fn read_narrow_types() {
+        let bits = Writer::<u128>::default()
+            .write(3, 5u8)
+            .write(1, 1u8)
+            .write(16, 0xbeefu16)
+            .write(108, 7u8)
+            .finish();
+
+        let mut r = Reader::new(bits);
+
+        assert_eq!(r.read_as::<u8>(3), 5);
+        assert!(r.read_as::<bool>(1));
+        assert_eq!(r.read_fixed::<u16, 16>(), 0xbeef);
+        assert_eq!(
+            r.try_read_as::<u64>(100),
+            Err(Error::InvalidWidth {
+                width: 100,
+                size: 64
+            })
+        );
+        assert_eq!(r.read_as::<u128>(108), 7);
+    }
+}
@@ -0,0 +1,76 @@
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Not enough bits left in the word for a field of `width` bits at `pos`.
+    OutOfBits { width: u8, pos: u16, size: u16 },
+    /// Field width exceeds the size of the word or the target integer.
+    InvalidWidth { width: u8, size: u16 },
+    /// Value has bits set above the field width.
+    ValueOverflow { width: u8, pos: u16, size: u16 },
+    /// Position lies past the end of the word.
+    InvalidPosition { pos: u16, size: u16 },
+    /// Enum discriminant read at `pos` does not match any variant.
+    InvalidDiscriminant { width: u8, pos: u16, size: u16 },
+    /// Byte stream ended after `pos` bits.
+    EndOfStream { pos: u64 },
+    /// Variable-length code at `pos` is malformed, or its value lies outside
+    /// the range the code supports.
+    InvalidCode { pos: u16, size: u16 },
+    /// Variable-length code at `pos` is longer than the shortest encoding of
+    /// its value.
+    NonCanonical { pos: u16, size: u16 },
+    /// Byte slice has `len` bytes where `expected` were needed.
+    InvalidByteLength { len: usize, expected: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::OutOfBits { width, pos, size } => write!(
+                f,
+                "{width}-bit field at bit {pos} exceeds the {size}-bit word"
+            ),
+            Error::InvalidWidth { width, size } => {
+                write!(f, "{width}-bit field is wider than {size} bits")
+            }
+            Error::ValueOverflow { width, pos, size } => write!(
+                f,
+                "value does not fit into the {width}-bit field at bit {pos} of the {size}-bit word"
+            ),
+            Error::InvalidPosition { pos, size } => {
+                write!(
+                    f,
+                    "bit position {pos} is past the end of the {size}-bit word"
+                )
+            }
+            Error::InvalidDiscriminant { width, pos, size } => write!(
+                f,
+                "unknown {width}-bit discriminant at bit {pos} of the {size}-bit word"
+            ),
+            Error::EndOfStream { pos } => write!(f, "unexpected end of stream at bit {pos}"),
+            Error::InvalidCode { pos, size } => write!(
+                f,
+                "invalid variable-length code at bit {pos} of the {size}-bit word"
+            ),
+            Error::NonCanonical { pos, size } => write!(
+                f,
+                "overlong variable-length code at bit {pos} of the {size}-bit word"
+            ),
+            Error::InvalidByteLength { len, expected } => {
+                write!(f, "expected {expected} bytes, found {len}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[track_caller]
+pub(crate) fn unwrap<T>(result: Result<T, Error>) -> T {
+    match result {
+        Ok(v) => v,
+        Err(e) => panic!("{}", e),
+    }
+}
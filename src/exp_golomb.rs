@@ -0,0 +1,285 @@
+//! Exponential-Golomb codes of order `k`, as used for the `ue(v)` and `se(v)`
+//! syntax elements of H.264 and HEVC (with `k = 0`).
+
+use crate::{bit_size, error, BitOrder, Error, Padding, Reader, Uint, UniversalCode, Writer};
+
+const MAX_ORDER: u8 = 64;
+
+/// Unsigned Exp-Golomb code of order `k`, for use where a `UniversalCode` is
+/// expected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpGolomb {
+    k: u8,
+}
+
+impl ExpGolomb {
+    #[track_caller]
+    pub const fn new(k: u8) -> Self {
+        assert!(k <= MAX_ORDER, "Exp-Golomb order must be at most 64");
+
+        ExpGolomb { k }
+    }
+
+    #[inline]
+    pub const fn k(&self) -> u8 {
+        self.k
+    }
+}
+
+impl UniversalCode for ExpGolomb {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        w: Writer<T, O>,
+        value: u64,
+    ) -> Result<Writer<T, O>, Error> {
+        w.try_write_exp_golomb(self.k, value)
+    }
+
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error> {
+        r.try_read_exp_golomb(self.k)
+    }
+}
+
+impl<T: Uint, O: BitOrder> Writer<T, O> {
+    #[track_caller]
+    pub fn write_exp_golomb(self, k: u8, value: u64) -> Self {
+        error::unwrap(self.try_write_exp_golomb(k, value))
+    }
+
+    pub fn try_write_exp_golomb(self, k: u8, value: u64) -> Result<Self, Error> {
+        self.write_code_num(k, u128::from(value))
+    }
+
+    #[track_caller]
+    pub fn write_signed_exp_golomb(self, k: u8, value: i64) -> Self {
+        error::unwrap(self.try_write_signed_exp_golomb(k, value))
+    }
+
+    /// Maps positive values to odd and non-positive values to even code
+    /// numbers: 0, 1, -1, 2, -2, ...
+    pub fn try_write_signed_exp_golomb(self, k: u8, value: i64) -> Result<Self, Error> {
+        let value = i128::from(value);
+        let code_num = if value > 0 { 2 * value - 1 } else { -2 * value };
+
+        self.write_code_num(k, code_num as u128)
+    }
+
+    /// Writes `code_num + 2^k` in binary, preceded by one zero for every bit
+    /// after the first that exceeds the `k` suffix bits.
+    fn write_code_num(self, k: u8, code_num: u128) -> Result<Self, Error> {
+        // An order beyond the largest code is not a valid code parameter.
+        if k > MAX_ORDER {
+            return Err(Error::InvalidCode {
+                pos: self.len,
+                size: bit_size::<T>(),
+            });
+        }
+
+        let x = code_num + (1 << k);
+        let count = (u128::BITS - x.leading_zeros()) as u8;
+
+        self.try_pad(count - 1 - k, Padding::Zeros)?
+            .try_write_u128(count, x)
+    }
+}
+
+impl<T: Uint, O: BitOrder> Reader<T, O> {
+    #[track_caller]
+    pub fn read_exp_golomb(&mut self, k: u8) -> u64 {
+        error::unwrap(self.try_read_exp_golomb(k))
+    }
+
+    /// On error, the reader stays at the start of the code.
+    pub fn try_read_exp_golomb(&mut self, k: u8) -> Result<u64, Error> {
+        self.rewind_on_error(|r| {
+            let pos = r.pos;
+            let code_num = r.read_code_num(k)?;
+
+            u64::try_from(code_num).map_err(|_| r.invalid_code(pos))
+        })
+    }
+
+    #[track_caller]
+    pub fn read_signed_exp_golomb(&mut self, k: u8) -> i64 {
+        error::unwrap(self.try_read_signed_exp_golomb(k))
+    }
+
+    pub fn try_read_signed_exp_golomb(&mut self, k: u8) -> Result<i64, Error> {
+        self.rewind_on_error(|r| {
+            let pos = r.pos;
+            let code_num = r.read_code_num(k)? as i128;
+            let value = if code_num % 2 == 1 {
+                (code_num + 1) / 2
+            } else {
+                -code_num / 2
+            };
+
+            i64::try_from(value).map_err(|_| r.invalid_code(pos))
+        })
+    }
+
+    fn read_code_num(&mut self, k: u8) -> Result<u128, Error> {
+        let pos = self.pos;
+        let mut zeros = 0;
+
+        if k > MAX_ORDER {
+            return Err(self.invalid_code(pos));
+        }
+
+        while !self.try_read_bool()? {
+            zeros += 1;
+
+            // The largest supported code number, 2^64, has a 65-bit suffix.
+            if zeros + k > MAX_ORDER + 1 {
+                return Err(self.invalid_code(pos));
+            }
+        }
+
+        let suffix = self.try_read_u128(zeros + k)?;
+
+        Ok((1 << (zeros + k) | suffix) - (1 << k))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{check_vectors, read_back};
+    use crate::Limbs;
+
+    #[test]
+    fn unsigned_vectors() {
+        check_vectors(
+            &[
+                (0, "1"),
+                (1, "010"),
+                (2, "011"),
+                (3, "00100"),
+                (6, "00111"),
+                (7, "0001000"),
+                (8, "0001001"),
+                (254, "000000011111111"),
+            ],
+            |w, v| w.write_exp_golomb(0, v),
+            |r| r.read_exp_golomb(0),
+        );
+        check_vectors(
+            &[
+                (0, "10"),
+                (1, "11"),
+                (2, "0100"),
+                (5, "0111"),
+                (6, "001000"),
+            ],
+            |w, v| w.write_exp_golomb(1, v),
+            |r| r.read_exp_golomb(1),
+        );
+        check_vectors(
+            &[(0, "1000"), (7, "1111"), (8, "010000"), (29, "00100101")],
+            |w, v| w.write_exp_golomb(3, v),
+            |r| r.read_exp_golomb(3),
+        );
+    }
+
+    #[test]
+    fn signed_vectors() {
+        check_vectors(
+            &[
+                (0, "1"),
+                (1, "010"),
+                (-1, "011"),
+                (2, "00100"),
+                (-2, "00101"),
+                (3, "00110"),
+                (-3, "00111"),
+                (4, "0001000"),
+            ],
+            |w, v| w.write_signed_exp_golomb(0, v),
+            |r| r.read_signed_exp_golomb(0),
+        );
+        check_vectors(
+            &[(0, "10"), (1, "11"), (-1, "0100"), (-3, "001000")],
+            |w, v| w.write_signed_exp_golomb(1, v),
+            |r| r.read_signed_exp_golomb(1),
+        );
+    }
+
+    #[test]
+    fn extremes() {
+        for k in [0, 1, 63, 64] {
+            for value in [u64::MAX, u64::MAX - 1, 1 << 63] {
+                let mut r = read_back(Writer::<Limbs<3>>::new().write_exp_golomb(k, value));
+
+                assert_eq!(r.read_exp_golomb(k), value);
+            }
+
+            for value in [i64::MIN, i64::MAX] {
+                let mut r = read_back(Writer::<Limbs<3>>::new().write_signed_exp_golomb(k, value));
+
+                assert_eq!(r.read_signed_exp_golomb(k), value);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Writer::<u64>::new().try_write_exp_golomb(65, 0).err(),
+            Some(Error::InvalidCode { pos: 0, size: 64 })
+        );
+        assert_eq!(
+            Reader::<u64>::new(u64::MAX).try_read_exp_golomb(65),
+            Err(Error::InvalidCode { pos: 0, size: 64 })
+        );
+        assert_eq!(
+            Writer::<u8>::new().try_write_exp_golomb(0, 16).err(),
+            Some(Error::OutOfBits {
+                width: 5,
+                pos: 4,
+                size: 8
+            })
+        );
+
+        // 66 leading zeros exceed the 65-bit suffix of the largest code.
+        let mut r = Reader::<u128>::new(1 << 61);
+
+        assert_eq!(
+            r.try_read_exp_golomb(0),
+            Err(Error::InvalidCode { pos: 0, size: 128 })
+        );
+
+        // Decodes to 2^64, which only the signed mapping can represent.
+        let mut r = read_back(Writer::<Limbs<3>>::new().write_signed_exp_golomb(0, i64::MIN));
+
+        assert_eq!(
+            r.try_read_exp_golomb(0),
+            Err(Error::InvalidCode { pos: 0, size: 129 })
+        );
+        assert_eq!(r.read_signed_exp_golomb(0), i64::MIN);
+
+        let mut r = Reader::<u32>::new(0);
+
+        assert_eq!(
+            r.try_read_exp_golomb(0),
+            Err(Error::OutOfBits {
+                width: 1,
+                pos: 32,
+                size: 32
+            })
+        );
+        assert_eq!(r.position(), 0);
+
+        // The suffix of a code started at bit 0 runs past the end.
+        let mut r = Reader::<u8>::new(0b11);
+
+        assert_eq!(
+            r.try_read_exp_golomb(0),
+            Err(Error::OutOfBits {
+                width: 6,
+                pos: 7,
+                size: 8
+            })
+        );
+        assert_eq!(r.position(), 0);
+    }
+}
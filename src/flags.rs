@@ -0,0 +1,95 @@
+use crate::{error, n_bit_mask, BitOrder, Error, Reader, Uint, Writer};
+
+impl<T: Uint, O: BitOrder> Writer<T, O> {
+    #[track_caller]
+    pub fn write_bool(self, flag: bool) -> Self {
+        error::unwrap(self.try_write_bool(flag))
+    }
+
+    pub fn try_write_bool(mut self, flag: bool) -> Result<Self, Error> {
+        self.push(1, if flag { n_bit_mask(1) } else { T::MIN })?;
+
+        Ok(self)
+    }
+
+    #[track_caller]
+    pub fn write_flags<I: IntoIterator<Item = bool>>(self, flags: I) -> Self {
+        error::unwrap(self.try_write_flags(flags))
+    }
+
+    pub fn try_write_flags<I: IntoIterator<Item = bool>>(self, flags: I) -> Result<Self, Error> {
+        flags
+            .into_iter()
+            .try_fold(self, |w, flag| w.try_write_bool(flag))
+    }
+}
+
+impl<T: Uint, O: BitOrder> Reader<T, O> {
+    #[track_caller]
+    pub fn read_bool(&mut self) -> bool {
+        error::unwrap(self.try_read_bool())
+    }
+
+    pub fn try_read_bool(&mut self) -> Result<bool, Error> {
+        self.try_read_next(1).map(|bit| bit != T::MIN)
+    }
+
+    #[track_caller]
+    pub fn read_flags<const N: usize>(&mut self) -> [bool; N] {
+        error::unwrap(self.try_read_flags())
+    }
+
+    /// On error, the reader stays where the first flag starts.
+    pub fn try_read_flags<const N: usize>(&mut self) -> Result<[bool; N], Error> {
+        self.rewind_on_error(|r| {
+            let mut flags = [false; N];
+
+            for flag in flags.iter_mut() {
+                *flag = r.try_read_bool()?;
+            }
+
+            Ok(flags)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_read_flags() {
+        let bits = Writer::<u8>::default()
+            .write_bool(true)
+            .write_flags([false, true, true])
+            .write_flags((0..4).map(|i| i % 2 == 0))
+            .finish();
+
+        assert_eq!(bits, 0b1011_1010);
+
+        let mut r = Reader::new(bits);
+
+        assert!(r.read_bool());
+        assert_eq!(r.read_flags(), [false, true, true]);
+        assert_eq!(r.read_flags(), [true, false]);
+        assert_eq!(
+            r.try_read_flags::<3>(),
+            Err(Error::OutOfBits {
+                width: 1,
+                pos: 8,
+                size: 8
+            })
+        );
+        assert_eq!(r.position(), 6);
+        assert_eq!(r.read_flags(), [true, false]);
+        assert!(r.try_read_bool().is_err());
+    }
+
+    #[test]
+    fn write_flags_overflow() {
+        assert!(Writer::<u8>::default()
+            .write(6, 0u8)
+            .try_write_flags([true; 3])
+            .is_err());
+    }
+}
@@ -0,0 +1,276 @@
+//! Golomb and Rice codes for geometrically distributed values: the quotient
+//! `value / m` in unary, followed by the remainder in truncated binary.
+
+use crate::{BitOrder, Error, Reader, Uint, Unary, UniversalCode, Writer};
+
+/// Golomb code with divisor `m`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Golomb {
+    m: u64,
+}
+
+/// Rice code, the Golomb code with divisor `2^k`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rice {
+    k: u8,
+}
+
+impl Golomb {
+    #[track_caller]
+    pub const fn new(m: u64) -> Self {
+        assert!(m > 0, "Golomb divisor must be positive");
+
+        Golomb { m }
+    }
+
+    #[inline]
+    pub const fn m(&self) -> u64 {
+        self.m
+    }
+
+    /// Picks the divisor that minimizes the encoded size of `values` among
+    /// those close to `0.69 * mean`, the optimum for geometric distributions.
+    pub fn optimal(values: &[u64]) -> Self {
+        if values.is_empty() {
+            return Golomb::new(1);
+        }
+
+        let sum: u128 = values.iter().map(|&v| u128::from(v)).sum();
+        let estimate = (sum / values.len() as u128 * 69_315 / 100_000) as u64;
+        let cost = |m: u64| -> u128 {
+            let code = Golomb::new(m);
+
+            values.iter().map(|&v| code.bits(v)).sum()
+        };
+
+        let m = (estimate.saturating_sub(16).max(1)..=estimate.saturating_add(16))
+            .min_by_key(|&m| cost(m))
+            .unwrap_or(1);
+
+        Golomb::new(m)
+    }
+
+    /// Number of remainder bits, `ceil(log2(m))`.
+    #[inline]
+    fn width(&self) -> u8 {
+        (u64::BITS - (self.m - 1).leading_zeros()) as u8
+    }
+
+    /// Remainders below the cutoff take one bit less than the others.
+    #[inline]
+    fn cutoff(&self) -> u128 {
+        (1 << self.width()) - u128::from(self.m)
+    }
+
+    fn bits(&self, value: u64) -> u128 {
+        let rem = u128::from(value % self.m);
+        let width = u128::from(self.width());
+
+        u128::from(value / self.m) + 1 + width - u128::from(rem < self.cutoff())
+    }
+}
+
+impl Rice {
+    #[track_caller]
+    pub const fn new(k: u8) -> Self {
+        assert!(k < 64, "Rice parameter must be less than 64");
+
+        Rice { k }
+    }
+
+    #[inline]
+    pub const fn k(&self) -> u8 {
+        self.k
+    }
+
+    /// Picks the parameter that minimizes the encoded size of `values`.
+    pub fn optimal(values: &[u64]) -> Self {
+        let cost = |k: u8| -> u128 {
+            values
+                .iter()
+                .map(|&v| u128::from(v >> k) + 1 + u128::from(k))
+                .sum()
+        };
+
+        Rice::new((0..64).min_by_key(|&k| cost(k)).unwrap_or(0))
+    }
+}
+
+impl From<Rice> for Golomb {
+    #[inline]
+    fn from(rice: Rice) -> Self {
+        Golomb::new(1 << rice.k)
+    }
+}
+
+impl UniversalCode for Golomb {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        w: Writer<T, O>,
+        value: u64,
+    ) -> Result<Writer<T, O>, Error> {
+        let w = Unary.encode(w, value / self.m)?;
+        let rem = u128::from(value % self.m);
+        let (width, cutoff) = (self.width(), self.cutoff());
+
+        if rem < cutoff {
+            w.try_write_u128(width - 1, rem)
+        } else {
+            w.try_write_u128(width, rem + cutoff)
+        }
+    }
+
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error> {
+        let pos = r.pos;
+        let quotient = Unary.decode(r)?;
+        let (width, cutoff) = (self.width(), self.cutoff());
+
+        let rem = if width == 0 {
+            0
+        } else {
+            let rem = r.try_read_u128(width - 1)?;
+
+            if rem < cutoff {
+                rem
+            } else {
+                (rem << 1 | r.try_read_u128(1)?) - cutoff
+            }
+        };
+
+        quotient
+            .checked_mul(self.m)
+            .and_then(|v| v.checked_add(rem as u64))
+            .ok_or_else(|| r.invalid_code(pos))
+    }
+}
+
+impl UniversalCode for Rice {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        w: Writer<T, O>,
+        value: u64,
+    ) -> Result<Writer<T, O>, Error> {
+        let w = Unary.encode(w, value >> self.k)?;
+
+        w.try_write_u128(self.k, u128::from(value) & ((1 << self.k) - 1))
+    }
+
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error> {
+        let pos = r.pos;
+        let quotient = Unary.decode(r)?;
+
+        if quotient > u64::MAX >> self.k {
+            return Err(r.invalid_code(pos));
+        }
+
+        Ok(quotient << self.k | r.try_read_u128(self.k)? as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{check_code, read_back};
+    use crate::Limbs;
+
+    #[test]
+    fn vectors() {
+        check_code(
+            Golomb::new(3),
+            &[(0, "00"), (1, "010"), (2, "011"), (3, "100"), (7, "11010")],
+        );
+        check_code(
+            Golomb::new(5),
+            &[
+                (0, "000"),
+                (2, "010"),
+                (3, "0110"),
+                (4, "0111"),
+                (9, "10111"),
+            ],
+        );
+        check_code(Golomb::new(1), &[(0, "0"), (3, "1110")]);
+        check_code(Rice::new(2), &[(0, "000"), (5, "1001"), (9, "11001")]);
+        check_code(Rice::new(0), &[(2, "110")]);
+    }
+
+    #[test]
+    fn rice_matches_golomb() {
+        for k in [0, 1, 5] {
+            for value in [0, 1, 7, 40, 100] {
+                let rice = Writer::<u128>::new().write_code(&Rice::new(k), value);
+                let golomb = Writer::<u128>::new().write_code(&Golomb::from(Rice::new(k)), value);
+
+                assert_eq!(rice.bits_written(), golomb.bits_written());
+                assert_eq!(rice.finish(), golomb.finish());
+            }
+        }
+    }
+
+    #[test]
+    fn bulk() {
+        let values = [3, 0, 17, 250, 9, 1, 64, 5];
+        let code = Golomb::optimal(&values);
+        let mut r = read_back(Writer::<Limbs<4>>::new().write_codes(&code, &values));
+        let mut out = [0; 8];
+
+        r.read_codes(&code, &mut out);
+
+        assert_eq!(out, values);
+        assert!(r.is_exhausted());
+    }
+
+    #[test]
+    fn optimal_parameters() {
+        let values = [12, 3, 40, 7, 0, 22, 9, 15, 31, 5, 2, 18];
+        let size = |code: Golomb| -> u128 { values.iter().map(|&v| code.bits(v)).sum() };
+
+        let rice = Rice::optimal(&values);
+
+        assert!((0..64).all(|k| size(Rice::new(rice.k()).into()) <= size(Rice::new(k).into())));
+
+        let golomb = Golomb::optimal(&values);
+
+        assert!((1..200).all(|m| size(golomb) <= size(Golomb::new(m))));
+        assert_eq!(Rice::optimal(&[0, 0, 1]), Rice::new(0));
+        assert_eq!(Golomb::optimal(&[]), Golomb::new(1));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Writer::<u32>::new().try_write_code(&Rice::new(0), 40).err(),
+            Some(Error::OutOfBits {
+                width: 8,
+                pos: 32,
+                size: 32
+            })
+        );
+
+        // Quotient of 2 with `m = u64::MAX` overflows.
+        let mut r = read_back(
+            Writer::<u128>::new()
+                .write_code(&Unary, 2)
+                .write(64, u64::MAX),
+        );
+
+        assert_eq!(
+            r.try_read_code(&Golomb::new(u64::MAX)),
+            Err(Error::InvalidCode { pos: 0, size: 67 })
+        );
+        assert_eq!(r.position(), 0);
+
+        // The remainder of a Rice code runs past the end.
+        let mut r = read_back(Writer::<u16>::new().write_code(&Unary, 3));
+
+        assert_eq!(
+            r.try_read_code(&Rice::new(4)),
+            Err(Error::OutOfBits {
+                width: 4,
+                pos: 4,
+                size: 4
+            })
+        );
+        assert_eq!(r.position(), 0);
+    }
+}
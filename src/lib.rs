@@ -1,11 +1,96 @@
-use std::mem::size_of;
-use std::ops::{BitAnd, BitOrAssign, ShlAssign, Shr};
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[macro_use]
+mod macros;
+
+mod bytes;
+mod const_fn;
+mod convert;
+mod error;
+mod exp_golomb;
+mod flags;
+mod golomb;
+mod limbs;
+mod order;
+mod pack;
+#[cfg(feature = "alloc")]
+mod packed_vec;
+mod padding;
+mod signed;
+mod stream;
+#[cfg(test)]
+mod test_util;
+mod universal;
+mod varint;
+mod zigzag;
+
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::num::{Saturating, Wrapping};
+use core::ops::{BitAnd, BitOrAssign};
+
+pub use bytes::{Endian, WordBytes};
+pub use convert::FromBits;
+pub use error::Error;
+pub use exp_golomb::ExpGolomb;
+pub use golomb::{Golomb, Rice};
+pub use limbs::{LimbBytes, Limbs};
+pub use order::{BitOrder, Lsb0, Msb0};
+pub use pack::BitPack;
+#[cfg(feature = "alloc")]
+pub use packed_vec::{PackedVec, PackedVecIter};
+pub use padding::Padding;
+pub use signed::Signed;
+pub use stream::{BitStreamReader, BitStreamWriter, ByteSink, ByteSource};
+pub use universal::{EliasDelta, EliasGamma, EliasOmega, Unary, UniversalCode};
+pub use varint::{Leb128, QuicVarint};
+pub use zigzag::{zigzag_decode, zigzag_encode};
+
+#[cfg(feature = "std")]
+pub use stream::{IoSink, IoSource};
+
+#[cfg(feature = "derive")]
+pub use uint_bits_derive::BitPack;
+
+#[doc(hidden)]
+pub use pack::private as __private;
+
+/// Unsigned integer usable as a packed word.
+///
+/// Shifts are methods rather than `Shl`/`Shr` supertraits because
+/// `Saturating` has no shift operators and `Wrapping` only shifts by `usize`.
+/// They are named `*_bits` so they don't clash with `Shl::shl`/`Shr::shr`.
 pub trait Uint<Rhs = Self, Output = Self>:
-    ShlAssign<u8> + BitAnd<Rhs, Output = Output> + BitOrAssign<Rhs> + Shr<u8, Output = Output> + Copy
+    BitAnd<Rhs, Output = Output> + BitOrAssign<Rhs> + Copy + PartialOrd
 {
     const MIN: Self;
     const MAX: Self;
+
+    /// Byte array of the same size as the integer.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Copy;
+
+    /// Shifts left by `n` bits. `n` must be less than the bit size of `Self`;
+    /// larger shifts may panic.
+    fn shl_bits(self, n: u8) -> Self;
+
+    /// Shifts right by `n` bits. `n` must be less than the bit size of `Self`;
+    /// larger shifts may panic.
+    fn shr_bits(self, n: u8) -> Self;
+
+    /// Returns the big-endian bytes of the integer.
+    fn to_be_bytes(self) -> Self::Bytes;
+
+    /// Returns the little-endian bytes of the integer.
+    fn to_le_bytes(self) -> Self::Bytes;
+
+    /// Builds the integer from its big-endian bytes.
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Builds the integer from its little-endian bytes.
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
 }
 
 macro_rules! impl_uint {
@@ -14,56 +99,432 @@ macro_rules! impl_uint {
             impl Uint for $Ty {
                 const MAX: Self = <$Ty>::MAX;
                 const MIN: Self = <$Ty>::MIN;
+
+                type Bytes = [u8; size_of::<$Ty>()];
+
+                #[inline]
+                fn shl_bits(self, n: u8) -> Self {
+                    self << n
+                }
+
+                #[inline]
+                fn shr_bits(self, n: u8) -> Self {
+                    self >> n
+                }
+
+                #[inline]
+                fn to_be_bytes(self) -> Self::Bytes {
+                    <$Ty>::to_be_bytes(self)
+                }
+
+                #[inline]
+                fn to_le_bytes(self) -> Self::Bytes {
+                    <$Ty>::to_le_bytes(self)
+                }
+
+                #[inline]
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$Ty>::from_be_bytes(bytes)
+                }
+
+                #[inline]
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$Ty>::from_le_bytes(bytes)
+                }
+            }
+        )+
+    };
+
+    ( $Wrapper:ident: $($Ty:ty),+ ) => {
+        $(
+            impl Uint for $Wrapper<$Ty> {
+                const MAX: Self = $Wrapper(<$Ty>::MAX);
+                const MIN: Self = $Wrapper(<$Ty>::MIN);
+
+                type Bytes = [u8; size_of::<$Ty>()];
+
+                #[inline]
+                fn shl_bits(self, n: u8) -> Self {
+                    $Wrapper(self.0 << n)
+                }
+
+                #[inline]
+                fn shr_bits(self, n: u8) -> Self {
+                    $Wrapper(self.0 >> n)
+                }
+
+                #[inline]
+                fn to_be_bytes(self) -> Self::Bytes {
+                    self.0.to_be_bytes()
+                }
+
+                #[inline]
+                fn to_le_bytes(self) -> Self::Bytes {
+                    self.0.to_le_bytes()
+                }
+
+                #[inline]
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    $Wrapper(<$Ty>::from_be_bytes(bytes))
+                }
+
+                #[inline]
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    $Wrapper(<$Ty>::from_le_bytes(bytes))
+                }
             }
         )+
     };
 }
 
-impl_uint!(u8, u16, u32, u64, u128);
+impl_uint!(u8, u16, u32, u64, u128, usize);
+impl_uint!(Wrapping: u8, u16, u32, u64, u128, usize);
+impl_uint!(Saturating: u8, u16, u32, u64, u128, usize);
 
-const fn bit_size<T: Uint>() -> u8 {
-    (8 * size_of::<T>()) as u8
+const fn bit_size<T: Uint>() -> u16 {
+    (8 * size_of::<T>()) as u16
 }
 
 #[inline]
 fn n_bit_mask<T: Uint>(n: u8) -> T {
-    T::MAX >> (bit_size::<T>() - n)
+    if n == 0 {
+        return T::MIN;
+    }
+
+    T::MAX.shr_bits((bit_size::<T>() - u16::from(n)) as u8)
+}
+
+/// Converts the low bits of `bits` to a word, dropping those that don't fit.
+#[inline]
+fn word_from_u128<T: Uint>(bits: u128) -> T {
+    let mut word = T::MIN.to_be_bytes();
+    let (dst, src) = (word.as_mut(), bits.to_be_bytes());
+    let n = dst.len().min(src.len());
+    let start = dst.len() - n;
+
+    dst[start..].copy_from_slice(&src[src.len() - n..]);
+
+    T::from_be_bytes(word)
+}
+
+/// Converts the low 128 bits of a word to a `u128`.
+#[inline]
+fn u128_from_word<T: Uint>(word: T) -> u128 {
+    let (src, mut bits) = (word.to_be_bytes(), [0; 16]);
+    let src = src.as_ref();
+    let n = src.len().min(bits.len());
+
+    bits[16 - n..].copy_from_slice(&src[src.len() - n..]);
+
+    u128::from_be_bytes(bits)
+}
+
+#[inline]
+fn check_width<T: Uint>(count: u8) -> Result<(), Error> {
+    let size = bit_size::<T>();
+
+    if u16::from(count) > size {
+        return Err(Error::InvalidWidth { width: count, size });
+    }
+
+    Ok(())
+}
+
+/// Writers are `Copy`: the `try_*` methods consume a copy, so the bits
+/// written before a failed call stay available in the caller's writer.
+#[derive(Debug, Clone, Copy)]
+pub struct Writer<T: Uint, O: BitOrder = Msb0> {
+    bit_vec: T,
+    len: u16,
+    order: PhantomData<O>,
 }
 
-#[derive(Default)]
-pub struct Writer<T: Uint>(T);
+impl<T: Uint> Default for Writer<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl<T: Uint> Writer<T> {
-    pub fn write<B: Into<T>>(mut self, count: u8, src: B) -> Self {
-        self.0 <<= count;
-        self.0 |= src.into() & n_bit_mask(count);
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_order(Msb0)
+    }
+}
+
+impl<T: Uint, O: BitOrder> Writer<T, O> {
+    /// Creates a writer that fills the word in the given bit order.
+    #[inline]
+    pub const fn with_order(_order: O) -> Self {
+        Writer {
+            bit_vec: T::MIN,
+            len: 0,
+            order: PhantomData,
+        }
+    }
+
+    #[track_caller]
+    pub fn write<B: Into<T>>(self, count: u8, src: B) -> Self {
+        error::unwrap(self.try_write(count, src))
+    }
+
+    pub fn try_write<B: Into<T>>(mut self, count: u8, src: B) -> Result<Self, Error> {
+        self.push(count, src.into())?;
+
+        Ok(self)
+    }
+
+    #[track_caller]
+    pub fn write_checked<B: Into<T>>(self, count: u8, src: B) -> Self {
+        error::unwrap(self.try_write_checked(count, src))
+    }
+
+    pub fn try_write_checked<B: Into<T>>(mut self, count: u8, src: B) -> Result<Self, Error> {
+        check_width::<T>(count)?;
+
+        let src = src.into();
+
+        if src > n_bit_mask(count) {
+            return Err(Error::ValueOverflow {
+                width: count,
+                pos: self.len,
+                size: bit_size::<T>(),
+            });
+        }
+
+        self.push(count, src)?;
+
+        Ok(self)
+    }
 
-        self
+    #[track_caller]
+    pub fn write_saturating<B: Into<T>>(self, count: u8, src: B) -> Self {
+        error::unwrap(self.try_write_saturating(count, src))
     }
 
-    pub fn finish(self) -> T {
-        self.0
+    pub fn try_write_saturating<B: Into<T>>(mut self, count: u8, src: B) -> Result<Self, Error> {
+        check_width::<T>(count)?;
+
+        let src = src.into();
+        let max = n_bit_mask(count);
+
+        self.push(count, if src > max { max } else { src })?;
+
+        Ok(self)
+    }
+
+    fn push(&mut self, count: u8, bits: T) -> Result<(), Error> {
+        check_width::<T>(count)?;
+
+        if u16::from(count) > self.remaining() {
+            return Err(Error::OutOfBits {
+                width: count,
+                pos: self.len,
+                size: bit_size::<T>(),
+            });
+        }
+
+        self.bit_vec = O::insert(self.bit_vec, self.len, count, bits & n_bit_mask(count));
+        self.len += u16::from(count);
+
+        Ok(())
+    }
+
+    /// Writes a field of up to 128 bits given as a `u128`, for the codes that
+    /// compute their fields independently of the word type.
+    pub(crate) fn try_write_u128(mut self, count: u8, bits: u128) -> Result<Self, Error> {
+        check_width::<u128>(count)?;
+
+        if count < 128 && bits >> count != 0 {
+            return Err(Error::ValueOverflow {
+                width: count,
+                pos: self.len,
+                size: bit_size::<T>(),
+            });
+        }
+
+        self.push(count, word_from_u128(bits))?;
+
+        Ok(self)
+    }
+
+    #[inline]
+    pub const fn bits_written(&self) -> u16 {
+        self.len
+    }
+
+    #[inline]
+    pub const fn remaining(&self) -> u16 {
+        bit_size::<T>() - self.len
+    }
+
+    pub const fn finish(self) -> T {
+        self.bit_vec
     }
 }
 
-pub struct Reader<T: Uint> {
+#[derive(Debug, Clone, Copy)]
+pub struct Reader<T: Uint, O: BitOrder = Msb0> {
     bit_vec: T,
-    pos: u8,
+    pos: u16,
+    len: u16,
+    order: PhantomData<O>,
 }
 
 impl<T: Uint> Reader<T> {
     #[inline]
-    pub fn new(bit_vec: T) -> Self {
-        Reader { bit_vec, pos: 0 }
+    pub const fn new(bit_vec: T) -> Self {
+        Self::with_order(bit_vec, Msb0)
+    }
+}
+
+impl<T: Uint, O: BitOrder> Reader<T, O> {
+    /// Creates a reader over a word filled in the given bit order.
+    #[inline]
+    pub const fn with_order(bit_vec: T, _order: O) -> Self {
+        Reader {
+            bit_vec,
+            pos: 0,
+            len: bit_size::<T>(),
+            order: PhantomData,
+        }
+    }
+
+    /// Reads only the first `len` bits, e.g. the output of a `Writer` that
+    /// wrote `len` bits without filling the whole word.
+    #[track_caller]
+    pub fn with_len(bit_vec: T, len: u16) -> Self {
+        error::unwrap(Self::try_with_len(bit_vec, len))
     }
 
+    pub fn try_with_len(bit_vec: T, len: u16) -> Result<Self, Error> {
+        let size = bit_size::<T>();
+
+        if len > size {
+            return Err(Error::InvalidPosition { pos: len, size });
+        }
+
+        Ok(Reader {
+            bit_vec,
+            pos: 0,
+            len,
+            order: PhantomData,
+        })
+    }
+
+    #[track_caller]
     pub fn read_next(&mut self, count: u8) -> T {
-        let shift = bit_size::<T>() - count - self.pos;
-        let bits = (self.bit_vec >> shift) & n_bit_mask(count);
+        error::unwrap(self.try_read_next(count))
+    }
+
+    pub fn try_read_next(&mut self, count: u8) -> Result<T, Error> {
+        let bits = self.try_peek(count)?;
+
+        self.pos += u16::from(count);
+
+        Ok(bits)
+    }
+
+    /// Reads a field of up to 128 bits as a `u128`.
+    pub(crate) fn try_read_u128(&mut self, count: u8) -> Result<u128, Error> {
+        check_width::<u128>(count)?;
+
+        self.try_read_next(count).map(u128_from_word)
+    }
+
+    #[track_caller]
+    pub fn peek(&self, count: u8) -> T {
+        error::unwrap(self.try_peek(count))
+    }
+
+    pub fn try_peek(&self, count: u8) -> Result<T, Error> {
+        self.check_bits(count)?;
+
+        Ok(O::extract(self.bit_vec, self.len, self.pos, count))
+    }
+
+    #[track_caller]
+    pub fn skip(&mut self, count: u8) {
+        error::unwrap(self.try_skip(count))
+    }
+
+    pub fn try_skip(&mut self, count: u8) -> Result<(), Error> {
+        self.check_bits(count)?;
+        self.pos += u16::from(count);
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn seek(&mut self, pos: u16) {
+        error::unwrap(self.try_seek(pos))
+    }
+
+    pub fn try_seek(&mut self, pos: u16) -> Result<(), Error> {
+        if pos > self.len {
+            return Err(Error::InvalidPosition {
+                pos,
+                size: self.len,
+            });
+        }
 
-        self.pos += count;
+        self.pos = pos;
 
-        bits
+        Ok(())
+    }
+
+    #[inline]
+    pub const fn position(&self) -> u16 {
+        self.pos
+    }
+
+    #[inline]
+    pub const fn remaining(&self) -> u16 {
+        self.len - self.pos
+    }
+
+    #[inline]
+    pub const fn is_exhausted(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    fn check_bits(&self, count: u8) -> Result<(), Error> {
+        check_width::<T>(count)?;
+
+        if u16::from(count) > self.remaining() {
+            return Err(Error::OutOfBits {
+                width: count,
+                pos: self.pos,
+                size: self.len,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Runs a read of several fields, rewinding to where it started if any of
+    /// them fails.
+    fn rewind_on_error<R>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let pos = self.pos;
+        let result = read(self);
+
+        if result.is_err() {
+            self.pos = pos;
+        }
+
+        result
+    }
+
+    /// Error for a variable-length code starting at `pos`.
+    fn invalid_code(&self, pos: u16) -> Error {
+        Error::InvalidCode {
+            pos,
+            size: self.len,
+        }
     }
 }
 
@@ -91,4 +552,224 @@ mod test {
         assert_eq!(r.read_next(3), 1);
         assert_eq!(r.read_next(57), 12);
     }
+
+    #[test]
+    fn try_read_past_end() {
+        let mut r = Reader::<u16>::new(0xabcd);
+
+        assert_eq!(r.try_read_next(12), Ok(0xabc));
+        assert_eq!(
+            r.try_read_next(5),
+            Err(Error::OutOfBits {
+                width: 5,
+                pos: 12,
+                size: 16
+            })
+        );
+        assert_eq!(r.try_read_next(4), Ok(0xd));
+        assert_eq!(
+            r.try_read_next(1),
+            Err(Error::OutOfBits {
+                width: 1,
+                pos: 16,
+                size: 16
+            })
+        );
+    }
+
+    #[test]
+    fn try_invalid_width() {
+        let mut r = Reader::<u8>::new(0);
+
+        assert_eq!(
+            r.try_read_next(9),
+            Err(Error::InvalidWidth { width: 9, size: 8 })
+        );
+        assert_eq!(
+            Writer::<u32>::default().try_write(33, 1u8).err(),
+            Some(Error::InvalidWidth {
+                width: 33,
+                size: 32
+            })
+        );
+    }
+
+    #[test]
+    fn edge_widths() {
+        macro_rules! check {
+            ( $($Ty:ty),+ ) => {
+                $(
+                    let size = bit_size::<$Ty>() as u8;
+                    let (min, max) = (<$Ty as Uint>::MIN, <$Ty as Uint>::MAX);
+
+                    for count in 0..=size {
+                        let bits = Writer::<$Ty>::default()
+                            .write(0, max)
+                            .write(count, max)
+                            .write(size - count, min)
+                            .write(0, max)
+                            .finish();
+
+                        let mut r = Reader::new(bits);
+
+                        assert_eq!(r.read_next(0), min);
+                        assert_eq!(r.read_next(count), n_bit_mask::<$Ty>(count));
+                        assert_eq!(r.read_next(size - count), min);
+                        assert_eq!(r.read_next(0), min);
+                        assert!(r.try_read_next(1).is_err());
+                    }
+                )+
+            };
+        }
+
+        check!(u8, u16, u32, u64, u128, usize);
+        check!(Wrapping<u8>, Wrapping<u32>, Wrapping<u128>, Wrapping<usize>);
+        check!(Saturating<u16>, Saturating<u64>, Saturating<usize>);
+    }
+
+    #[test]
+    fn u128_fields() {
+        let w = Writer::<Limbs<3>>::new()
+            .try_write_u128(100, u128::MAX >> 28)
+            .and_then(|w| w.try_write_u128(3, 5))
+            .unwrap();
+
+        assert_eq!(
+            w.try_write_u128(2, 4).err(),
+            Some(Error::ValueOverflow {
+                width: 2,
+                pos: 103,
+                size: 192
+            })
+        );
+
+        let mut r = Reader::<Limbs<3>>::with_len(w.finish(), 103);
+
+        assert_eq!(r.try_read_u128(100), Ok(u128::MAX >> 28));
+        assert_eq!(r.try_read_u128(3), Ok(5));
+
+        let mut r = Reader::new(Wrapping(0xa5u8));
+
+        assert_eq!(r.try_read_u128(8), Ok(0xa5));
+        assert_eq!(word_from_u128::<u16>(0x12_3456), 0x3456);
+    }
+
+    #[test]
+    fn usize_and_wrappers() {
+        assert_eq!(u32::from(bit_size::<usize>()), usize::BITS);
+
+        let bits = Writer::<usize>::default()
+            .write(4, 9u8)
+            .write(8, 200usize)
+            .finish();
+        let mut r = Reader::<usize>::with_len(bits, 12);
+
+        assert_eq!(r.read_next(4), 9);
+        assert_eq!(r.read_signed::<isize>(8), -56);
+
+        let bits = Writer::<Wrapping<u32>>::default()
+            .write(12, Wrapping(0xabc))
+            .write(20, Wrapping(u32::MAX))
+            .finish();
+        let mut r = Reader::<Wrapping<u32>>::new(bits);
+
+        assert_eq!(bits, Wrapping(0xabcf_ffff));
+        assert_eq!(r.read_next(12), Wrapping(0xabc));
+
+        let bits = Writer::<Saturating<u16>, _>::with_order(Lsb0)
+            .write_saturating(4, Saturating(100))
+            .write(4, Saturating(1))
+            .finish();
+
+        assert_eq!(bits, Saturating(0x1f));
+    }
+
+    #[test]
+    fn write_capacity() {
+        let w = Writer::<u16>::default().write(5, 3u8).write(7, 1u8);
+
+        assert_eq!(w.bits_written(), 12);
+        assert_eq!(w.remaining(), 4);
+        assert_eq!(
+            w.try_write(5, 0u8).err(),
+            Some(Error::OutOfBits {
+                width: 5,
+                pos: 12,
+                size: 16
+            })
+        );
+
+        // The failed write left `w` intact.
+        assert_eq!(w.write(4, 0xfu8).finish(), 3 << 11 | 1 << 4 | 0xf);
+    }
+
+    #[test]
+    fn write_checked_and_saturating() {
+        let bits = Writer::<u32>::default()
+            .write(8, 300u16)
+            .write_saturating(8, 300u16)
+            .write_checked(8, 255u16)
+            .finish();
+
+        assert_eq!(bits, 0x2c_ff_ff);
+        assert_eq!(
+            Writer::<u32>::default()
+                .write(4, 1u8)
+                .try_write_checked(8, 300u16)
+                .err(),
+            Some(Error::ValueOverflow {
+                width: 8,
+                pos: 4,
+                size: 32
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the 32-bit word")]
+    fn write_overflow_panics() {
+        Writer::<u32>::default().write(20, 1u32).write(13, 1u32);
+    }
+
+    #[test]
+    fn navigation() {
+        let mut r = Reader::<u16>::new(0xabcd);
+
+        assert_eq!(r.peek(8), 0xab);
+        assert_eq!(r.position(), 0);
+
+        r.skip(4);
+        assert_eq!(r.read_next(8), 0xbc);
+        assert_eq!(r.position(), 12);
+        assert_eq!(r.remaining(), 4);
+        assert_eq!(
+            r.try_skip(5),
+            Err(Error::OutOfBits {
+                width: 5,
+                pos: 12,
+                size: 16
+            })
+        );
+
+        r.seek(16);
+        assert!(r.is_exhausted());
+        assert_eq!(r.try_peek(0), Ok(0));
+        assert_eq!(
+            r.try_seek(17),
+            Err(Error::InvalidPosition { pos: 17, size: 16 })
+        );
+
+        r.seek(4);
+        assert_eq!(r.peek(4), 0xb);
+        assert!(!r.is_exhausted());
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the 8-bit word")]
+    fn read_past_end_panics() {
+        let mut r = Reader::<u8>::new(0);
+
+        r.read_next(6);
+        r.read_next(3);
+    }
 }
@@ -0,0 +1,287 @@
+use core::cmp::Ordering;
+use core::ops::{BitAnd, BitOrAssign};
+
+use crate::{bit_size, Error, Uint};
+
+/// Unsigned integer of `64 * N` bits for words wider than `u128`, stored as
+/// `N` 64-bit limbs, least significant limb first.
+///
+/// `N` must be between 2 and 4, so that every shift and field width fits the
+/// `u8` counts used throughout the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limbs<const N: usize>(pub [u64; N]);
+
+impl<const N: usize> Limbs<N> {
+    const ZERO: Self = {
+        assert!(N >= 2 && N <= 4, "`Limbs` supports 2 to 4 limbs");
+
+        Limbs([0; N])
+    };
+}
+
+/// Byte representation of `Limbs<N>`, `8 * N` bytes long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LimbBytes<const N: usize>([[u8; 8]; N]);
+
+impl<const N: usize> AsRef<[u8]> for LimbBytes<N> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_flattened()
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for LimbBytes<N> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.0.as_flattened_mut()
+    }
+}
+
+impl<const N: usize> Default for Limbs<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize> Uint for Limbs<N> {
+    const MIN: Self = Self::ZERO;
+    const MAX: Self = {
+        let _ = Self::ZERO;
+
+        Limbs([u64::MAX; N])
+    };
+
+    type Bytes = LimbBytes<N>;
+
+    fn shl_bits(self, n: u8) -> Self {
+        let (limbs, bits) = (usize::from(n) / 64, u32::from(n) % 64);
+        let mut res = Self::ZERO;
+
+        for i in (limbs..N).rev() {
+            res.0[i] = self.0[i - limbs] << bits;
+
+            if bits > 0 && i > limbs {
+                res.0[i] |= self.0[i - limbs - 1] >> (64 - bits);
+            }
+        }
+
+        res
+    }
+
+    fn shr_bits(self, n: u8) -> Self {
+        let (limbs, bits) = (usize::from(n) / 64, u32::from(n) % 64);
+        let mut res = Self::ZERO;
+
+        for i in 0..N.saturating_sub(limbs) {
+            res.0[i] = self.0[i + limbs] >> bits;
+
+            if bits > 0 && i + limbs + 1 < N {
+                res.0[i] |= self.0[i + limbs + 1] << (64 - bits);
+            }
+        }
+
+        res
+    }
+
+    fn to_be_bytes(self) -> LimbBytes<N> {
+        let mut bytes = LimbBytes([[0; 8]; N]);
+
+        for (b, l) in bytes.0.iter_mut().zip(self.0.iter().rev()) {
+            *b = l.to_be_bytes();
+        }
+
+        bytes
+    }
+
+    fn to_le_bytes(self) -> LimbBytes<N> {
+        LimbBytes(self.0.map(u64::to_le_bytes))
+    }
+
+    fn from_be_bytes(bytes: LimbBytes<N>) -> Self {
+        let mut res = Self::ZERO;
+
+        for (l, b) in res.0.iter_mut().zip(bytes.0.iter().rev()) {
+            *l = u64::from_be_bytes(*b);
+        }
+
+        res
+    }
+
+    fn from_le_bytes(bytes: LimbBytes<N>) -> Self {
+        let _ = Self::ZERO;
+
+        Limbs(bytes.0.map(u64::from_le_bytes))
+    }
+}
+
+impl<const N: usize> BitAnd for Limbs<N> {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(mut self, rhs: Self) -> Self {
+        for (l, r) in self.0.iter_mut().zip(rhs.0) {
+            *l &= r;
+        }
+
+        self
+    }
+}
+
+impl<const N: usize> BitOrAssign for Limbs<N> {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        for (l, r) in self.0.iter_mut().zip(rhs.0) {
+            *l |= r;
+        }
+    }
+}
+
+impl<const N: usize> Ord for Limbs<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const N: usize> PartialOrd for Limbs<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_from {
+    ( $($Ty:ty),+ ) => {
+        $(
+            impl<const N: usize> From<$Ty> for Limbs<N> {
+                #[inline]
+                fn from(v: $Ty) -> Self {
+                    let mut res = Self::ZERO;
+
+                    res.0[0] = v as u64;
+                    res.0[1] = (v as u128 >> 64) as u64;
+
+                    res
+                }
+            }
+
+            impl<const N: usize> TryFrom<Limbs<N>> for $Ty {
+                type Error = Error;
+
+                fn try_from(v: Limbs<N>) -> Result<Self, Error> {
+                    let low = u128::from(v.0[0]) | u128::from(v.0[1]) << 64;
+
+                    if v.0[2..].iter().any(|&l| l != 0) || low > <$Ty>::MAX as u128 {
+                        return Err(Error::ValueOverflow {
+                            width: <$Ty>::BITS as u8,
+                            pos: 0,
+                            size: bit_size::<Limbs<N>>(),
+                        });
+                    }
+
+                    Ok(low as $Ty)
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(u8, u16, u32, u64, u128);
+
+impl<const N: usize> From<bool> for Limbs<N> {
+    #[inline]
+    fn from(v: bool) -> Self {
+        Self::from(u8::from(v))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{n_bit_mask, Lsb0, Reader, Writer};
+
+    #[test]
+    fn shifts() {
+        let v = Limbs([0x8000_0000_0000_0001, 0, 0, 0]);
+
+        assert_eq!(v.shl_bits(1), Limbs([2, 1, 0, 0]));
+        assert_eq!(
+            v.shl_bits(127),
+            Limbs([0, 0x8000_0000_0000_0000, 0x4000_0000_0000_0000, 0])
+        );
+        assert_eq!(v.shl_bits(255), Limbs([0, 0, 0, 0x8000_0000_0000_0000]));
+        assert_eq!(v.shl_bits(64).shr_bits(64), v);
+        assert_eq!(Limbs::<4>::MAX.shr_bits(255), Limbs([1, 0, 0, 0]));
+        assert_eq!(n_bit_mask::<Limbs<3>>(65), Limbs([u64::MAX, 1, 0]));
+        assert!(Limbs([0, 0, 1]) > Limbs([u64::MAX, u64::MAX, 0]));
+    }
+
+    #[test]
+    fn straddling_fields() {
+        let bits = Writer::<Limbs<4>>::default()
+            .write(100, u128::MAX)
+            .write(60, 0xabc_u64)
+            .write(32, 7u32)
+            .write(64, 0xdead_beef_u64)
+            .finish();
+
+        let mut r = Reader::<Limbs<4>>::new(bits);
+
+        assert_eq!(r.read_as::<u128>(100), u128::MAX >> 28);
+        assert_eq!(r.read_as::<u64>(60), 0xabc);
+        assert_eq!(r.read_as::<u32>(32), 7);
+        assert_eq!(r.read_next(64), Limbs::from(0xdead_beef_u64));
+        assert!(r.is_exhausted());
+
+        let bits = Writer::<Limbs<3>, _>::with_order(Lsb0)
+            .write(70, u128::MAX)
+            .write_signed(100, -5i128)
+            .finish();
+
+        let mut r = Reader::<Limbs<3>, _>::with_order(bits, Lsb0);
+
+        assert_eq!(r.read_as::<u128>(70), u128::MAX >> 58);
+        assert_eq!(r.read_signed::<i128>(100), -5);
+        assert_eq!(r.remaining(), 22);
+    }
+
+    #[test]
+    fn edge_widths() {
+        for count in 0..=255u8 {
+            let bits = Writer::<Limbs<4>>::default()
+                .write(count, Limbs::<4>::MAX)
+                .fill(crate::Padding::Zeros)
+                .finish();
+
+            let mut r = Reader::<Limbs<4>>::new(bits);
+
+            assert_eq!(r.read_next(count), n_bit_mask::<Limbs<4>>(count));
+            r.skip(255 - count);
+            assert_eq!(r.read_next(1), Limbs::MIN);
+            assert!(r.is_exhausted());
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let v = Limbs([0x0102_0304_0506_0708, 0x090a_0b0c_0d0e_0f10]);
+        let be = v.to_be_bytes();
+
+        assert_eq!(be.as_ref()[..3], [0x09, 0x0a, 0x0b]);
+        assert_eq!(be.as_ref()[13..], [0x06, 0x07, 0x08]);
+        assert_eq!(v.to_le_bytes().as_ref()[..2], [0x08, 0x07]);
+        assert_eq!(Limbs::from_be_bytes(be), v);
+        assert_eq!(Limbs::from_le_bytes(v.to_le_bytes()), v);
+        assert_eq!(
+            u128::from_be_bytes(be.as_ref().try_into().unwrap()),
+            u128::try_from(v).unwrap()
+        );
+    }
+
+    #[test]
+    fn narrowing() {
+        assert_eq!(u8::try_from(Limbs([255, 0, 0])), Ok(255));
+        assert!(u8::try_from(Limbs([256, 0, 0])).is_err());
+        assert!(u128::try_from(Limbs([0, 0, 1])).is_err());
+    }
+}
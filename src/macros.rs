@@ -0,0 +1,191 @@
+/// Declares a struct whose fields are packed into a single `Uint` word, most
+/// significant field first, with the same semantics as `Writer`/`Reader`.
+///
+/// Setters panic on values wider than their field. Naming a third accessor
+/// adds a `try_*` setter that returns the error instead.
+///
+/// ```
+/// uint_bits::bitfields! {
+///     #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+///     pub struct Header: u16 {
+///         pub version, set_version: u8 = 3,
+///         pub urgent, set_urgent: bool = 1,
+///         pub length, set_length, try_set_length: u16 = 12,
+///     }
+/// }
+///
+/// let mut hdr = Header::default();
+///
+/// hdr.set_version(5);
+/// hdr.set_length(1000);
+///
+/// assert!(hdr.try_set_length(4096).is_err());
+/// assert_eq!(hdr.pack(), 0b101_0_001111101000);
+/// assert_eq!(Header::unpack(hdr.pack()), hdr);
+/// ```
+#[macro_export]
+macro_rules! bitfields {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $Name:ident: $Ty:ty {
+            $(
+                $(#[$field_attr:meta])*
+                $field_vis:vis $get:ident, $set:ident $(, $try_set:ident)?: $FTy:ty = $width:expr
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $Name {
+            $( $get: $FTy, )*
+        }
+
+        const _: () = {
+            assert!(
+                0 $(+ $width as usize)* <= 8 * ::core::mem::size_of::<$Ty>(),
+                concat!("fields of `", stringify!($Name), "` do not fit into `", stringify!($Ty), "`")
+            );
+            $(
+                assert!(
+                    $width as u16 <= <$FTy as $crate::FromBits<$Ty>>::BITS,
+                    concat!("field `", stringify!($get), "` is wider than `", stringify!($FTy), "`")
+                );
+            )*
+        };
+
+        impl $Name {
+            #[track_caller]
+            pub fn pack(&self) -> $Ty {
+                $crate::Writer::<$Ty>::new()
+                    $( .write_checked($width, self.$get) )*
+                    .finish()
+            }
+
+            pub fn unpack(bits: $Ty) -> Self {
+                let mut r = $crate::Reader::<$Ty>::with_len(bits, 0 $(+ $width as u16)*);
+
+                $Name {
+                    $( $get: r.read_as($width), )*
+                }
+            }
+
+            $(
+                $(#[$field_attr])*
+                #[inline]
+                $field_vis fn $get(&self) -> $FTy {
+                    self.$get
+                }
+            )*
+        }
+
+        $crate::bitfields!(@setters $Name: $Ty, 0u16; $(
+            $field_vis $get, $set $(, $try_set)?: $FTy = $width;
+        )+);
+    };
+
+    (@setters $Name:ident: $Ty:ty, $pos:expr;) => {};
+
+    (
+        @setters $Name:ident: $Ty:ty, $pos:expr;
+        $field_vis:vis $get:ident, $set:ident $(, $try_set:ident)?: $FTy:ty = $width:expr;
+        $($rest:tt)*
+    ) => {
+        impl $Name {
+            #[track_caller]
+            $field_vis fn $set(&mut self, value: $FTy) {
+                if let Err(e) = $crate::__private::check_field::<$Ty, $FTy>(value, $width, $pos) {
+                    panic!("{}", e);
+                }
+
+                self.$get = value;
+            }
+
+            $(
+                $field_vis fn $try_set(
+                    &mut self,
+                    value: $FTy,
+                ) -> ::core::result::Result<(), $crate::Error> {
+                    $crate::__private::check_field::<$Ty, $FTy>(value, $width, $pos)?;
+                    self.$get = value;
+
+                    Ok(())
+                }
+            )?
+        }
+
+        $crate::bitfields!(@setters $Name: $Ty, $pos + $width as u16; $($rest)*);
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Error, Reader};
+
+    bitfields! {
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+        struct Frame: u64 {
+            kind, set_kind: u8 = 4,
+            ack, set_ack: bool = 1,
+            seq, set_seq, try_set_seq: u16 = 11,
+            payload, set_payload: u32 = 32,
+        }
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let mut frame = Frame::default();
+
+        frame.set_kind(9);
+        frame.set_ack(true);
+        frame.set_seq(2047);
+        frame.set_payload(0xdead_beef);
+
+        let bits = frame.pack();
+        let mut r = Reader::<u64>::with_len(bits, 48);
+
+        assert_eq!(r.read_next(4), 9);
+        assert_eq!(r.read_next(1), 1);
+        assert_eq!(r.read_next(11), 2047);
+        assert_eq!(r.read_next(32), 0xdead_beef);
+
+        let frame = Frame::unpack(bits);
+
+        assert_eq!(frame.kind(), 9);
+        assert!(frame.ack());
+        assert_eq!(frame.seq(), 2047);
+        assert_eq!(frame.payload(), 0xdead_beef);
+    }
+
+    #[test]
+    #[should_panic(expected = "11-bit field at bit 5 of the 64-bit word")]
+    fn setter_checks_width() {
+        Frame::default().set_seq(2048);
+    }
+
+    #[test]
+    fn try_setter() {
+        let mut frame = Frame::default();
+
+        assert_eq!(
+            frame.try_set_seq(2048),
+            Err(Error::ValueOverflow {
+                width: 11,
+                pos: 5,
+                size: 64
+            })
+        );
+        assert_eq!(frame.seq(), 0);
+        assert_eq!(frame.try_set_seq(2047), Ok(()));
+        assert_eq!(frame.seq(), 2047);
+    }
+
+    #[test]
+    #[should_panic(expected = "value does not fit")]
+    fn pack_checks_fields() {
+        let frame = Frame {
+            kind: 16,
+            ..Frame::default()
+        };
+
+        frame.pack();
+    }
+}
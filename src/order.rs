@@ -0,0 +1,130 @@
+use crate::{bit_size, n_bit_mask, Uint};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub trait BitOrder: sealed::Sealed + Copy {
+    /// Appends a masked `count`-bit field after the `len` bits already in `bit_vec`.
+    #[doc(hidden)]
+    fn insert<T: Uint>(bit_vec: T, len: u16, count: u8, bits: T) -> T;
+
+    /// Extracts the `count`-bit field that starts `pos` bits into the first
+    /// `len` bits of `bit_vec`.
+    #[doc(hidden)]
+    fn extract<T: Uint>(bit_vec: T, len: u16, pos: u16, count: u8) -> T;
+}
+
+/// The first field occupies the most significant bits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Msb0;
+
+/// The first field occupies the least significant bits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Lsb0;
+
+impl sealed::Sealed for Msb0 {}
+impl sealed::Sealed for Lsb0 {}
+
+impl BitOrder for Msb0 {
+    #[inline]
+    fn insert<T: Uint>(mut bit_vec: T, _len: u16, count: u8, bits: T) -> T {
+        if u16::from(count) < bit_size::<T>() {
+            bit_vec = bit_vec.shl_bits(count);
+        } else {
+            bit_vec = T::MIN;
+        }
+
+        bit_vec |= bits;
+
+        bit_vec
+    }
+
+    #[inline]
+    fn extract<T: Uint>(bit_vec: T, len: u16, pos: u16, count: u8) -> T {
+        if count == 0 {
+            return T::MIN;
+        }
+
+        let shift = (len - u16::from(count) - pos) as u8;
+
+        bit_vec.shr_bits(shift) & n_bit_mask(count)
+    }
+}
+
+impl BitOrder for Lsb0 {
+    #[inline]
+    fn insert<T: Uint>(mut bit_vec: T, len: u16, count: u8, bits: T) -> T {
+        if count == 0 {
+            return bit_vec;
+        }
+
+        bit_vec |= bits.shl_bits(len as u8);
+
+        bit_vec
+    }
+
+    #[inline]
+    fn extract<T: Uint>(bit_vec: T, _len: u16, pos: u16, count: u8) -> T {
+        if count == 0 {
+            return T::MIN;
+        }
+
+        bit_vec.shr_bits(pos as u8) & n_bit_mask(count)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Reader, Writer};
+
+    #[test]
+    fn lsb0_write_read() {
+        let bits = Writer::<u16, _>::with_order(Lsb0)
+            .write(3, 5u8)
+            .write_bool(true)
+            .write_signed(4, -2i8)
+            .write(8, 0xabu8)
+            .finish();
+
+        assert_eq!(bits, 0xabed);
+
+        let mut r = Reader::<u16, _>::with_order(bits, Lsb0);
+
+        assert_eq!(r.read_next(3), 5);
+        assert!(r.read_bool());
+        assert_eq!(r.read_signed::<i8>(4), -2);
+        assert_eq!(r.read_next(8), 0xab);
+        assert!(r.try_read_next(1).is_err());
+    }
+
+    #[test]
+    fn lsb0_partial_word() {
+        let bits = Writer::<u32, _>::with_order(Lsb0)
+            .write(5, 17u8)
+            .write(0, 1u8)
+            .write(7, 100u8)
+            .finish();
+
+        assert_eq!(bits, 100 << 5 | 17);
+
+        let mut r = Reader::<u32, _>::with_order(bits, Lsb0);
+
+        assert_eq!(r.read_next(5), 17);
+        assert_eq!(r.read_next(7), 100);
+        assert_eq!(r.read_next(20), 0);
+    }
+
+    #[test]
+    fn msb0_is_default() {
+        let bits = Writer::<u8>::default().write(2, 1u8).write(6, 3u8).finish();
+        let mut r = Reader::<u8, _>::with_order(bits, Msb0);
+
+        assert_eq!(r.read_next(2), 1);
+        assert_eq!(r.read_next(6), 3);
+
+        // The order is inferred without annotations.
+        assert_eq!(Reader::new(0xabu8).read_next(4), 0xa);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{bit_size, BitOrder, Error, FromBits, Reader, Uint, Writer};
+
+/// A type that packs into a fixed number of bits of a `T` word. Implemented
+/// for the primitive words and `bool`, and derived with `#[derive(BitPack)]`
+/// when the `derive` feature is enabled.
+pub trait BitPack<T: Uint>: Sized {
+    const BITS: u16;
+
+    fn write_bits<O: BitOrder>(&self, w: Writer<T, O>) -> Result<Writer<T, O>, Error>;
+
+    fn read_bits<O: BitOrder>(r: &mut Reader<T, O>) -> Result<Self, Error>;
+
+    fn pack(&self) -> Result<T, Error> {
+        const {
+            assert!(
+                Self::BITS <= bit_size::<T>(),
+                "type does not fit into the word"
+            )
+        }
+
+        self.write_bits(Writer::<T>::new()).map(Writer::finish)
+    }
+
+    fn unpack(bits: T) -> Result<Self, Error> {
+        const {
+            assert!(
+                Self::BITS <= bit_size::<T>(),
+                "type does not fit into the word"
+            )
+        }
+
+        Self::read_bits(&mut Reader::<T>::with_len(bits, Self::BITS))
+    }
+}
+
+impl<T: Uint, U: Uint + Into<T> + FromBits<T>> BitPack<T> for U {
+    const BITS: u16 = bit_size::<U>();
+
+    fn write_bits<O: BitOrder>(&self, w: Writer<T, O>) -> Result<Writer<T, O>, Error> {
+        const {
+            assert!(
+                Self::BITS <= u8::MAX as u16,
+                "type is wider than a single field"
+            )
+        }
+
+        w.try_write(Self::BITS as u8, *self)
+    }
+
+    fn read_bits<O: BitOrder>(r: &mut Reader<T, O>) -> Result<Self, Error> {
+        const {
+            assert!(
+                Self::BITS <= u8::MAX as u16,
+                "type is wider than a single field"
+            )
+        }
+
+        r.try_read_as(Self::BITS as u8)
+    }
+}
+
+impl<T: Uint> BitPack<T> for bool {
+    const BITS: u16 = 1;
+
+    fn write_bits<O: BitOrder>(&self, w: Writer<T, O>) -> Result<Writer<T, O>, Error> {
+        w.try_write_bool(*self)
+    }
+
+    fn read_bits<O: BitOrder>(r: &mut Reader<T, O>) -> Result<Self, Error> {
+        r.try_read_bool()
+    }
+}
+
+/// Support code for `#[derive(BitPack)]`, not a public API.
+pub mod private {
+    use crate::{bit_size, n_bit_mask, BitOrder, Error, Reader, Uint, Writer};
+
+    #[inline]
+    pub const fn fits(tag: u128, width: u8) -> bool {
+        width >= 128 || tag >> width == 0
+    }
+
+    #[inline]
+    pub const fn max(a: u16, b: u16) -> u16 {
+        if a > b {
+            a
+        } else {
+            b
+        }
+    }
+
+    /// Checks a `bitfields!` setter value against its field at `pos`.
+    pub fn check_field<T: Uint, B: Into<T>>(value: B, width: u8, pos: u16) -> Result<(), Error> {
+        if value.into() > n_bit_mask(width) {
+            return Err(Error::ValueOverflow {
+                width,
+                pos,
+                size: bit_size::<T>(),
+            });
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn write_tag<T: Uint, O: BitOrder>(
+        w: Writer<T, O>,
+        width: u8,
+        tag: u128,
+    ) -> Result<Writer<T, O>, Error> {
+        w.try_write_u128(width, tag)
+    }
+
+    #[inline]
+    pub fn read_tag<T: Uint, O: BitOrder>(r: &mut Reader<T, O>, width: u8) -> Result<u128, Error> {
+        r.try_read_u128(width)
+    }
+
+    pub fn unknown_tag<T: Uint, O: BitOrder>(r: &Reader<T, O>, width: u8) -> Error {
+        Error::InvalidDiscriminant {
+            width,
+            pos: r.pos - u16::from(width),
+            size: r.len,
+        }
+    }
+
+    pub fn pad<T: Uint, O: BitOrder>(
+        mut w: Writer<T, O>,
+        mut count: u16,
+    ) -> Result<Writer<T, O>, Error> {
+        while count > 0 {
+            let n = count.min(u16::from(u8::MAX)) as u8;
+
+            w.push(n, T::MIN)?;
+            count -= u16::from(n);
+        }
+
+        Ok(w)
+    }
+
+    pub fn skip<T: Uint, O: BitOrder>(r: &mut Reader<T, O>, mut count: u16) -> Result<(), Error> {
+        while count > 0 {
+            let n = count.min(u16::from(u8::MAX)) as u8;
+
+            r.try_skip(n)?;
+            count -= u16::from(n);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Lsb0;
+
+    #[test]
+    fn primitives() {
+        assert_eq!(BitPack::<u32>::pack(&0xabu8), Ok(0xab));
+        assert_eq!(<u8 as BitPack<u32>>::unpack(0xab), Ok(0xab));
+        assert_eq!(BitPack::<u8>::pack(&true), Ok(1));
+        assert_eq!(<bool as BitPack<u8>>::unpack(1), Ok(true));
+    }
+
+    #[test]
+    fn tags() {
+        let w = private::write_tag(Writer::<u16, _>::with_order(Lsb0), 3, 6).unwrap();
+        let w = private::pad(w, 13).unwrap();
+
+        assert_eq!(w.finish(), 6);
+        assert!(private::write_tag(Writer::<u16>::new(), 3, 8).is_err());
+
+        let mut r = Reader::<u16>::new(0b101 << 13);
+
+        assert_eq!(private::read_tag(&mut r, 3), Ok(5));
+        assert_eq!(
+            private::unknown_tag(&r, 3),
+            Error::InvalidDiscriminant {
+                width: 3,
+                pos: 0,
+                size: 16
+            }
+        );
+        assert_eq!(private::skip(&mut r, 13), Ok(()));
+        assert!(r.is_exhausted());
+    }
+}
@@ -0,0 +1,362 @@
+use alloc::vec::Vec;
+
+use crate::{bit_size, check_width, error, n_bit_mask, Error, Uint};
+
+/// Vector of `width`-bit integers stored back to back in `W` words, starting
+/// at the least significant bit of the first word. Elements may straddle two
+/// words. Bits past the last element are always zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PackedVec<W: Uint> {
+    words: Vec<W>,
+    width: u8,
+    len: usize,
+}
+
+impl<W: Uint> PackedVec<W> {
+    #[track_caller]
+    pub fn new(width: u8) -> Self {
+        error::unwrap(Self::try_new(width))
+    }
+
+    pub fn try_new(width: u8) -> Result<Self, Error> {
+        check_width::<W>(width)?;
+
+        Ok(PackedVec {
+            words: Vec::new(),
+            width,
+            len: 0,
+        })
+    }
+
+    #[track_caller]
+    pub fn with_capacity(width: u8, capacity: usize) -> Self {
+        let mut v = Self::new(width);
+
+        v.words.reserve(v.words_for(capacity));
+        v
+    }
+
+    #[inline]
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn as_words(&self) -> &[W] {
+        &self.words
+    }
+
+    pub fn get(&self, index: usize) -> Option<W> {
+        if index >= self.len {
+            return None;
+        }
+
+        if self.width == 0 {
+            return Some(W::MIN);
+        }
+
+        let (word, shift) = self.locate(index);
+        let mut v = self.words[word].shr_bits(shift);
+
+        if u16::from(shift) + u16::from(self.width) > bit_size::<W>() {
+            v |= self.words[word + 1].shl_bits((bit_size::<W>() - u16::from(shift)) as u8);
+        }
+
+        Some(v & n_bit_mask(self.width))
+    }
+
+    /// Replaces the element at `index`. Bits of `value` above the element
+    /// width are ignored, as with `Writer::write`.
+    #[track_caller]
+    pub fn set(&mut self, index: usize, value: W) {
+        assert!(
+            index < self.len,
+            "index {index} is out of bounds for length {}",
+            self.len
+        );
+
+        self.store(index, value & n_bit_mask(self.width));
+    }
+
+    pub fn push(&mut self, value: W) {
+        self.len += 1;
+        self.words.resize(self.words_for(self.len), W::MIN);
+        self.store(self.len - 1, value & n_bit_mask(self.width));
+    }
+
+    pub fn pop(&mut self) -> Option<W> {
+        let value = self.get(self.len.checked_sub(1)?)?;
+
+        self.truncate(self.len - 1);
+
+        Some(value)
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        self.len = len;
+        self.words.truncate(self.words_for(len));
+
+        let used = (len * usize::from(self.width) % usize::from(bit_size::<W>())) as u8;
+
+        if let Some(last) = self.words.last_mut().filter(|_| used > 0) {
+            *last = *last & n_bit_mask(used);
+        }
+    }
+
+    pub fn resize(&mut self, len: usize, value: W) {
+        if len <= self.len {
+            return self.truncate(len);
+        }
+
+        self.words.reserve(self.words_for(len) - self.words.len());
+
+        while self.len < len {
+            self.push(value);
+        }
+    }
+
+    pub fn iter(&self) -> PackedVecIter<'_, W> {
+        PackedVecIter {
+            vec: self,
+            range: 0..self.len,
+        }
+    }
+
+    fn words_for(&self, len: usize) -> usize {
+        (len * usize::from(self.width)).div_ceil(usize::from(bit_size::<W>()))
+    }
+
+    /// Returns the word holding the first bit of element `index` and the
+    /// offset of that bit.
+    #[inline]
+    fn locate(&self, index: usize) -> (usize, u8) {
+        let bit = index * usize::from(self.width);
+        let size = usize::from(bit_size::<W>());
+
+        (bit / size, (bit % size) as u8)
+    }
+
+    /// Stores a value that already fits the element width.
+    fn store(&mut self, index: usize, value: W) {
+        if self.width == 0 {
+            return;
+        }
+
+        let size = bit_size::<W>();
+        let (word, shift) = self.locate(index);
+        let end = u16::from(shift) + u16::from(self.width);
+
+        let mut keep = n_bit_mask::<W>(shift);
+
+        if end < size {
+            keep |= W::MAX.shl_bits(end as u8);
+        }
+
+        let mut bits = self.words[word] & keep;
+
+        bits |= value.shl_bits(shift);
+        self.words[word] = bits;
+
+        if end > size {
+            let mut bits = self.words[word + 1] & W::MAX.shl_bits((end - size) as u8);
+
+            bits |= value.shr_bits((size - u16::from(shift)) as u8);
+            self.words[word + 1] = bits;
+        }
+    }
+}
+
+impl<W: Uint> Extend<W> for PackedVec<W> {
+    fn extend<I: IntoIterator<Item = W>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, W: Uint> IntoIterator for &'a PackedVec<W> {
+    type Item = W;
+    type IntoIter = PackedVecIter<'a, W>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PackedVecIter<'a, W: Uint> {
+    vec: &'a PackedVec<W>,
+    range: core::ops::Range<usize>,
+}
+
+impl<W: Uint> Iterator for PackedVecIter<'_, W> {
+    type Item = W;
+
+    #[inline]
+    fn next(&mut self) -> Option<W> {
+        self.range.next().and_then(|i| self.vec.get(i))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<W: Uint> DoubleEndedIterator for PackedVecIter<'_, W> {
+    #[inline]
+    fn next_back(&mut self) -> Option<W> {
+        self.range.next_back().and_then(|i| self.vec.get(i))
+    }
+}
+
+impl<W: Uint> ExactSizeIterator for PackedVecIter<'_, W> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Limbs;
+
+    #[test]
+    fn straddling_elements() {
+        let mut v = PackedVec::<u8>::new(3);
+
+        v.extend((0..10).map(|i| i as u8));
+
+        assert_eq!(v.len(), 10);
+        assert_eq!(
+            v.as_words(),
+            [0b1000_1000, 0b1100_0110, 0b1111_1010, 0b0000_1000]
+        );
+        assert!(v.iter().eq((0..10).map(|i| i as u8 & 7)));
+
+        v.set(2, 5);
+        v.set(5, 0xff);
+
+        assert_eq!(v.get(1), Some(1));
+        assert_eq!(v.get(2), Some(5));
+        assert_eq!(v.get(3), Some(3));
+        assert_eq!(v.get(5), Some(7));
+        assert_eq!(v.get(10), None);
+    }
+
+    #[test]
+    fn push_pop_resize() {
+        let mut v = PackedVec::<u64>::with_capacity(17, 100);
+
+        for i in 0..100u64 {
+            v.push(i * 1301);
+        }
+
+        assert_eq!(v.as_words().len(), 27);
+        assert!(v
+            .iter()
+            .rev()
+            .eq((0..100).rev().map(|i| (i * 1301) & 0x1_ffff)));
+        assert_eq!(v.pop(), Some((99 * 1301) & 0x1_ffff));
+
+        v.resize(4, 0);
+
+        assert_eq!(v.as_words().len(), 2);
+
+        v.resize(6, 0x1_2345);
+
+        assert!(v.iter().eq([0, 1301, 2602, 3903, 0x1_2345, 0x1_2345]));
+
+        while v.pop().is_some() {}
+
+        assert!(v.is_empty());
+        assert!(v.as_words().is_empty());
+    }
+
+    #[test]
+    fn truncate_clears_unused_bits() {
+        let mut a = PackedVec::<u16>::new(5);
+        let mut b = PackedVec::<u16>::new(5);
+
+        a.extend([31, 31, 31, 31]);
+        a.truncate(2);
+        b.extend([31, 31]);
+
+        assert_eq!(a, b);
+
+        a.push(0);
+        b.push(0);
+
+        assert_eq!(a, b);
+        assert_eq!(a.get(2), Some(0));
+    }
+
+    #[test]
+    fn wide_words() {
+        let mut v = PackedVec::<Limbs<3>>::new(100);
+
+        for i in 0..7u128 {
+            v.push(Limbs::from(u128::MAX - i));
+        }
+
+        for i in 0..7u128 {
+            assert_eq!(
+                v.get(i as usize),
+                Some(Limbs::from((u128::MAX - i) & (u128::MAX >> 28)))
+            );
+        }
+
+        let mut v = PackedVec::<u32>::new(32);
+
+        v.extend([u32::MAX, 0, 7]);
+        v.set(1, 9);
+
+        assert_eq!(v.as_words(), [u32::MAX, 9, 7]);
+    }
+
+    #[test]
+    fn zero_width() {
+        let mut v = PackedVec::<u32>::new(0);
+
+        v.extend([5, 0, u32::MAX]);
+
+        assert_eq!(v.len(), 3);
+        assert!(v.as_words().is_empty());
+        assert_eq!(v.get(2), Some(0));
+        assert_eq!(v.get(3), None);
+
+        v.set(1, 9);
+
+        assert!(v.iter().eq([0, 0, 0]));
+        assert_eq!(v.pop(), Some(0));
+        assert_eq!(v.len(), 2);
+    }
+
+    #[test]
+    fn invalid_width() {
+        assert_eq!(
+            PackedVec::<u16>::try_new(17),
+            Err(Error::InvalidWidth {
+                width: 17,
+                size: 16
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn set_out_of_bounds() {
+        PackedVec::<u8>::new(4).set(0, 1);
+    }
+}
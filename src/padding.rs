@@ -0,0 +1,109 @@
+use crate::{error, n_bit_mask, BitOrder, Error, Msb0, Uint, Writer};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    #[default]
+    Zeros,
+    Ones,
+}
+
+impl Padding {
+    #[inline]
+    fn bits<T: Uint>(self, count: u8) -> T {
+        match self {
+            Padding::Zeros => T::MIN,
+            Padding::Ones => n_bit_mask(count),
+        }
+    }
+}
+
+impl<T: Uint, O: BitOrder> Writer<T, O> {
+    #[track_caller]
+    pub fn pad(self, count: u8, padding: Padding) -> Self {
+        error::unwrap(self.try_pad(count, padding))
+    }
+
+    pub fn try_pad(mut self, count: u8, padding: Padding) -> Result<Self, Error> {
+        self.push(count, padding.bits(count))?;
+
+        Ok(self)
+    }
+
+    /// Pads all remaining bits of the word.
+    pub fn fill(mut self, padding: Padding) -> Self {
+        while self.remaining() > 0 {
+            let count = self.remaining().min(u16::from(u8::MAX)) as u8;
+
+            self = self.pad(count, padding);
+        }
+
+        self
+    }
+}
+
+impl<T: Uint> Writer<T, Msb0> {
+    /// Finishes the word with the first field starting at the most
+    /// significant bit, so that it can be read back with `Reader::new`.
+    pub fn finish_left_aligned(self) -> T {
+        self.fill(Padding::Zeros).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lsb0, Reader};
+
+    #[test]
+    fn partial_word_round_trip() {
+        let w = Writer::<u32>::default().write(5, 17u8).write(7, 100u8);
+        let len = w.bits_written();
+        let bits = w.finish();
+
+        let mut r = Reader::<u32>::with_len(bits, len);
+
+        assert_eq!(r.read_next(5), 17);
+        assert_eq!(r.read_next(7), 100);
+        assert!(r.is_exhausted());
+
+        let bits = Writer::<u32>::default()
+            .write(5, 17u8)
+            .write(7, 100u8)
+            .finish_left_aligned();
+
+        assert_eq!(bits, (17 << 7 | 100) << 20);
+
+        let mut r = Reader::<u32>::new(bits);
+
+        assert_eq!(r.read_next(5), 17);
+        assert_eq!(r.read_next(7), 100);
+        assert_eq!(r.remaining(), 20);
+    }
+
+    #[test]
+    fn explicit_padding() {
+        let bits = Writer::<u16>::default()
+            .write(4, 0xau8)
+            .pad(4, Padding::Ones)
+            .write(4, 0u8)
+            .fill(Padding::Ones)
+            .finish();
+
+        assert_eq!(bits, 0xaf0f);
+
+        let bits = Writer::<u16, _>::with_order(Lsb0)
+            .write(4, 0xau8)
+            .fill(Padding::Ones)
+            .finish();
+
+        assert_eq!(bits, 0xfffa);
+        assert!(Writer::<u8>::default()
+            .fill(Padding::Zeros)
+            .try_pad(1, Padding::Zeros)
+            .is_err());
+        assert_eq!(
+            Reader::<u8>::try_with_len(0, 9).err(),
+            Some(Error::InvalidPosition { pos: 9, size: 8 })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::convert::cast;
+use crate::{bit_size, check_width, error, n_bit_mask, BitOrder, Error, Reader, Uint, Writer};
+
+pub trait Signed: Copy + PartialEq {
+    type Unsigned: Uint;
+
+    fn to_unsigned(self) -> Self::Unsigned;
+
+    /// Interprets the low `count` bits of `bits` as a two's-complement value.
+    /// `count` must not exceed the bit width of `Self`.
+    fn sign_extend(bits: Self::Unsigned, count: u8) -> Self;
+
+    /// Maps 0, -1, 1, -2, 2, ... to 0, 1, 2, 3, 4, ...
+    fn to_zigzag(self) -> Self::Unsigned;
+
+    fn from_zigzag(bits: Self::Unsigned) -> Self;
+}
+
+macro_rules! impl_signed {
+    ( $($Ty:ty => $Unsigned:ty),+ ) => {
+        $(
+            impl Signed for $Ty {
+                type Unsigned = $Unsigned;
+
+                #[inline]
+                fn to_unsigned(self) -> $Unsigned {
+                    self as $Unsigned
+                }
+
+                #[inline]
+                fn sign_extend(bits: $Unsigned, count: u8) -> Self {
+                    if count == 0 {
+                        return 0;
+                    }
+
+                    let shift = <$Ty>::BITS - u32::from(count);
+
+                    ((bits as $Ty) << shift) >> shift
+                }
+
+                #[inline]
+                fn to_zigzag(self) -> $Unsigned {
+                    ((self << 1) ^ (self >> (<$Ty>::BITS - 1))) as $Unsigned
+                }
+
+                #[inline]
+                fn from_zigzag(bits: $Unsigned) -> Self {
+                    (bits >> 1) as $Ty ^ -((bits & 1) as $Ty)
+                }
+            }
+        )+
+    };
+}
+
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+impl<T: Uint, O: BitOrder> Writer<T, O> {
+    #[track_caller]
+    pub fn write_signed<S>(self, count: u8, src: S) -> Self
+    where
+        S: Signed,
+        T: TryFrom<S::Unsigned>,
+    {
+        error::unwrap(self.try_write_signed(count, src))
+    }
+
+    pub fn try_write_signed<S>(mut self, count: u8, src: S) -> Result<Self, Error>
+    where
+        S: Signed,
+        T: TryFrom<S::Unsigned>,
+    {
+        check_width::<T>(count)?;
+        check_width::<S::Unsigned>(count)?;
+
+        let bits = src.to_unsigned() & n_bit_mask(count);
+
+        if S::sign_extend(bits, count) != src {
+            return Err(Error::ValueOverflow {
+                width: count,
+                pos: self.len,
+                size: bit_size::<T>(),
+            });
+        }
+
+        self.push(count, cast(bits))?;
+
+        Ok(self)
+    }
+}
+
+impl<T: Uint, O: BitOrder> Reader<T, O> {
+    #[track_caller]
+    pub fn read_signed<S>(&mut self, count: u8) -> S
+    where
+        S: Signed,
+        S::Unsigned: TryFrom<T>,
+    {
+        error::unwrap(self.try_read_signed(count))
+    }
+
+    pub fn try_read_signed<S>(&mut self, count: u8) -> Result<S, Error>
+    where
+        S: Signed,
+        S::Unsigned: TryFrom<T>,
+    {
+        check_width::<S::Unsigned>(count)?;
+
+        let bits = self.try_read_next(count)?;
+
+        Ok(S::sign_extend(cast(bits), count))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_read_signed() {
+        let bits = Writer::<u32>::default()
+            .write_signed(4, -8i8)
+            .write_signed(4, 7i8)
+            .write_signed(4, -1i64)
+            .write_signed(12, -2000i16)
+            .write_signed(8, 0i128)
+            .finish();
+
+        assert_eq!(bits, 0x87f8_3000);
+
+        let mut r = Reader::new(bits);
+
+        assert_eq!(r.read_signed::<i8>(4), -8);
+        assert_eq!(r.read_signed::<i8>(4), 7);
+        assert_eq!(r.read_signed::<i64>(4), -1);
+        assert_eq!(r.read_signed::<i16>(12), -2000);
+        assert_eq!(r.read_signed::<i128>(8), 0);
+    }
+
+    #[test]
+    fn signed_range() {
+        let w = Writer::<u16>::default();
+
+        assert_eq!(
+            w.try_write_signed(4, 8i8).err(),
+            Some(Error::ValueOverflow {
+                width: 4,
+                pos: 0,
+                size: 16
+            })
+        );
+        assert!(Writer::<u16>::default().try_write_signed(4, -9i32).is_err());
+        assert_eq!(
+            Writer::<u128>::default().try_write_signed(9, 1i8).err(),
+            Some(Error::InvalidWidth { width: 9, size: 8 })
+        );
+        assert_eq!(
+            Reader::new(u128::MAX).try_read_signed::<i8>(9),
+            Err(Error::InvalidWidth { width: 9, size: 8 })
+        );
+    }
+}
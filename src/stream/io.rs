@@ -0,0 +1,108 @@
+use std::io;
+
+use super::{ByteSink, ByteSource};
+use crate::Error;
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        let kind = match e {
+            Error::EndOfStream { .. } => io::ErrorKind::UnexpectedEof,
+            _ => io::ErrorKind::InvalidInput,
+        };
+
+        io::Error::new(kind, e)
+    }
+}
+
+/// Adapts any `io::Write` to a `ByteSink`.
+///
+/// `write_all` may pass part of the bytes to the writer before it fails, so a
+/// failed `BitStreamWriter::write` must not be retried on an `IoSink`.
+#[derive(Debug)]
+pub struct IoSink<W: io::Write>(pub W);
+
+impl<W: io::Write> IoSink<W> {
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+}
+
+impl<W: io::Write> ByteSink for IoSink<W> {
+    type Error = io::Error;
+
+    #[inline]
+    fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.0.write_all(bytes)
+    }
+}
+
+/// Adapts any `io::Read` to a `ByteSource`.
+#[derive(Debug)]
+pub struct IoSource<R: io::Read>(pub R);
+
+impl<R: io::Read> IoSource<R> {
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+impl<R: io::Read> ByteSource for IoSource<R> {
+    type Error = io::Error;
+
+    fn read_bytes(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.0.read(buf) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => return res,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{n_bit_mask, BitStreamReader, BitStreamWriter, Padding};
+
+    #[test]
+    fn io_sink() {
+        let mut w = BitStreamWriter::new(IoSink(Vec::new()));
+
+        w.write(3, 5u8).unwrap();
+        w.write(7, 0u8).unwrap();
+
+        let e = w.write(129, 0u8).unwrap_err();
+
+        assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            w.finish(Padding::Zeros).unwrap().into_inner(),
+            [0b1010_0000, 0]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut w = BitStreamWriter::new(IoSink(Vec::new()));
+
+        for i in 0..=128u8 {
+            w.write(i, u128::MAX / 3).unwrap();
+        }
+
+        let bytes = w.finish(Padding::Zeros).unwrap().into_inner();
+        let mut r = BitStreamReader::new(IoSource(&bytes[..]));
+
+        for i in 0..=128u8 {
+            assert_eq!(
+                r.read_next(i).unwrap(),
+                (u128::MAX / 3) & n_bit_mask::<u128>(i)
+            );
+        }
+
+        assert_eq!(r.position(), 8 * bytes.len() as u64);
+        assert_eq!(
+            r.read_next(1).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(r.peek(129).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,9 @@
+#[cfg(feature = "std")]
+mod io;
+mod reader;
+mod writer;
+
+#[cfg(feature = "std")]
+pub use io::{IoSink, IoSource};
+pub use reader::{BitStreamReader, ByteSource};
+pub use writer::{BitStreamWriter, ByteSink};
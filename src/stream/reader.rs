@@ -0,0 +1,230 @@
+use crate::{check_width, Error};
+
+pub trait ByteSource {
+    type Error: From<Error>;
+
+    /// Reads up to `buf.len()` bytes, returning 0 at the end of the stream.
+    fn read_bytes(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+impl ByteSource for &[u8] {
+    type Error = Error;
+
+    #[inline]
+    fn read_bytes(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let n = buf.len().min(self.len());
+        let (head, tail) = self.split_at(n);
+
+        buf[..n].copy_from_slice(head);
+        *self = tail;
+
+        Ok(n)
+    }
+}
+
+/// Reads fields of up to 128 bits from a byte stream, most significant bit
+/// first. Bytes are pulled from the source into a small buffer as needed, and
+/// a read or skip of up to 128 bits that runs past the end of the stream
+/// consumes nothing.
+#[derive(Debug)]
+pub struct BitStreamReader<S: ByteSource> {
+    source: S,
+    buf: [u8; BUF_LEN],
+    head: usize,
+    end: usize,
+    offset: u8,
+    pos: u64,
+    eof: bool,
+}
+
+/// Holds a 128-bit field at any bit offset.
+const BUF_LEN: usize = 24;
+
+impl<S: ByteSource> BitStreamReader<S> {
+    pub fn new(source: S) -> Self {
+        BitStreamReader {
+            source,
+            buf: [0; BUF_LEN],
+            head: 0,
+            end: 0,
+            offset: 0,
+            pos: 0,
+            eof: false,
+        }
+    }
+
+    pub fn read_next(&mut self, count: u8) -> Result<u128, S::Error> {
+        check_width::<u128>(count)?;
+        self.fill(count)?;
+
+        let bits = self.bits(count);
+
+        self.consume(u64::from(count));
+
+        Ok(bits)
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, S::Error> {
+        self.read_next(1).map(|bit| bit == 1)
+    }
+
+    /// Returns the next `count` bits without consuming them.
+    pub fn peek(&mut self, count: u8) -> Result<u128, S::Error> {
+        check_width::<u128>(count)?;
+        self.fill(count)?;
+
+        Ok(self.bits(count))
+    }
+
+    /// Skips `count` bits. Longer skips than 128 bits are not buffered, so one
+    /// that runs past the end of the stream consumes the rest of it.
+    pub fn skip(&mut self, mut count: u64) -> Result<(), S::Error> {
+        if count <= 128 {
+            self.fill(count as u8)?;
+            self.consume(count);
+
+            return Ok(());
+        }
+
+        while count > 0 {
+            self.fill(1)?;
+
+            let take = count.min(self.available());
+
+            self.consume(take);
+            count -= take;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    #[inline]
+    fn available(&self) -> u64 {
+        (8 * (self.end - self.head)) as u64 - u64::from(self.offset)
+    }
+
+    /// Buffers at least `count` bits, or fails at the end of the stream.
+    fn fill(&mut self, count: u8) -> Result<(), S::Error> {
+        if self.available() >= u64::from(count) {
+            return Ok(());
+        }
+
+        self.buf.copy_within(self.head..self.end, 0);
+        self.end -= self.head;
+        self.head = 0;
+
+        while !self.eof && self.available() < u64::from(count) {
+            let got = self.source.read_bytes(&mut self.buf[self.end..])?;
+
+            if got == 0 {
+                self.eof = true;
+            }
+
+            self.end += got;
+        }
+
+        if self.available() < u64::from(count) {
+            return Err(self.end_of_stream().into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the next `count` buffered bits.
+    fn bits(&self, count: u8) -> u128 {
+        if count == 0 {
+            return 0;
+        }
+
+        // Loads the field as one word. At a non-zero offset, a 128-bit field
+        // ends in a 17th byte.
+        let mut word = [0; 16];
+        let n = (self.end - self.head).min(word.len());
+
+        word[..n].copy_from_slice(&self.buf[self.head..self.head + n]);
+
+        let mut bits = u128::from_be_bytes(word) << self.offset;
+
+        if self.offset > 0 && self.head + 16 < self.end {
+            bits |= u128::from(self.buf[self.head + 16] >> (8 - self.offset));
+        }
+
+        bits >> (128 - count)
+    }
+
+    #[inline]
+    fn consume(&mut self, count: u64) {
+        let bit = u64::from(self.offset) + count;
+
+        self.head += (bit / 8) as usize;
+        self.offset = (bit % 8) as u8;
+        self.pos += count;
+    }
+
+    fn end_of_stream(&self) -> Error {
+        Error::EndOfStream {
+            pos: self.pos + self.available(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_across_bytes() {
+        let bytes = [0xab, 0xcd, 0xef, 0x01, 0x23, 0x45, 0x67, 0x89, 0xff];
+        let mut r = BitStreamReader::new(&bytes[..]);
+
+        assert_eq!(r.peek(12), Ok(0xabc));
+        assert_eq!(r.peek(64), Ok(0xabcd_ef01_2345_6789));
+        assert_eq!(r.read_next(4), Ok(0xa));
+        assert_eq!(r.read_next(60), Ok(0x0bcd_ef01_2345_6789));
+        assert_eq!(r.position(), 64);
+        assert_eq!(r.read_bool(), Ok(true));
+        assert_eq!(r.read_next(8), Err(Error::EndOfStream { pos: 72 }));
+        assert_eq!(r.skip(7), Ok(()));
+        assert_eq!(r.peek(0), Ok(0));
+        assert_eq!(r.skip(1), Err(Error::EndOfStream { pos: 72 }));
+    }
+
+    #[test]
+    fn failed_reads_consume_nothing() {
+        let bytes = [0x12; 17];
+        let mut r = BitStreamReader::new(&bytes[..]);
+
+        assert_eq!(r.read_next(3), Ok(0));
+        assert_eq!(
+            r.read_next(128),
+            Ok(0x9090_9090_9090_9090_9090_9090_9090_9090)
+        );
+        assert_eq!(r.read_next(6), Err(Error::EndOfStream { pos: 136 }));
+        assert_eq!(r.peek(5), Ok(0x12));
+        assert_eq!(r.position(), 131);
+        assert_eq!(r.read_next(5), Ok(0x12));
+
+        let mut r = BitStreamReader::new(&bytes[..9]);
+
+        assert_eq!(r.read_next(100), Err(Error::EndOfStream { pos: 72 }));
+        assert_eq!(r.position(), 0);
+        assert_eq!(r.skip(100), Err(Error::EndOfStream { pos: 72 }));
+        assert_eq!(r.position(), 0);
+        assert_eq!(r.skip(200), Err(Error::EndOfStream { pos: 72 }));
+        assert_eq!(r.position(), 72);
+
+        let mut r = BitStreamReader::new(&bytes[..]);
+
+        assert_eq!(r.skip(133), Ok(()));
+        assert_eq!(r.read_next(3), Ok(0b010));
+    }
+}
@@ -0,0 +1,183 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{check_width, n_bit_mask, Error, Padding};
+
+pub trait ByteSink {
+    type Error: From<Error>;
+
+    /// Writes all of `bytes`. A sink that writes none of them when it fails
+    /// lets a failed `BitStreamWriter::write` be retried.
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl ByteSink for Vec<u8> {
+    type Error = Error;
+
+    #[inline]
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.extend_from_slice(bytes);
+
+        Ok(())
+    }
+}
+
+/// Writes fields of up to 128 bits into a byte stream, most significant bit
+/// first. Whole bytes are passed to the sink as soon as they are complete.
+#[derive(Debug)]
+pub struct BitStreamWriter<S: ByteSink> {
+    sink: S,
+    acc: u8,
+    acc_len: u8,
+    len: u64,
+}
+
+impl<S: ByteSink> BitStreamWriter<S> {
+    pub fn new(sink: S) -> Self {
+        BitStreamWriter {
+            sink,
+            acc: 0,
+            acc_len: 0,
+            len: 0,
+        }
+    }
+
+    /// Writes the low `count` bits of `src`. If the sink fails, the writer is
+    /// left as it was before the call. Retrying is only safe if the sink wrote
+    /// none of the bytes, see `ByteSink::write_bytes`.
+    pub fn write<B: Into<u128>>(&mut self, count: u8, src: B) -> Result<(), S::Error> {
+        check_width::<u128>(count)?;
+
+        let bits = src.into() & n_bit_mask::<u128>(count);
+        let mut buf = [0; 17];
+        let mut n = 0;
+        let (mut acc, mut acc_len) = (self.acc, self.acc_len);
+        let mut remaining = count;
+
+        while remaining > 0 {
+            let take = remaining.min(8 - acc_len);
+            let chunk = (bits >> (remaining - take)) as u32 & n_bit_mask::<u32>(take);
+
+            acc = (u32::from(acc) << take | chunk) as u8;
+            acc_len += take;
+            remaining -= take;
+
+            if acc_len == 8 {
+                buf[n] = acc;
+                n += 1;
+                acc = 0;
+                acc_len = 0;
+            }
+        }
+
+        self.sink.write_bytes(&buf[..n])?;
+        self.acc = acc;
+        self.acc_len = acc_len;
+        self.len += u64::from(count);
+
+        Ok(())
+    }
+
+    pub fn write_bool(&mut self, flag: bool) -> Result<(), S::Error> {
+        self.write(1, flag)
+    }
+
+    #[inline]
+    pub fn bits_written(&self) -> u64 {
+        self.len
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Pads the last partial byte and returns the sink.
+    pub fn finish(mut self, padding: Padding) -> Result<S, S::Error> {
+        if self.acc_len > 0 {
+            let count = 8 - self.acc_len;
+            let pad = match padding {
+                Padding::Zeros => 0,
+                Padding::Ones => n_bit_mask::<u8>(count),
+            };
+
+            self.sink.write_bytes(&[self.acc << count | pad])?;
+        }
+
+        Ok(self.sink)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_bytes() {
+        let mut w = BitStreamWriter::new(Vec::new());
+
+        w.write(4, 0xau8).unwrap();
+        assert!(w.get_ref().is_empty());
+
+        w.write(12, 0xbcdu16).unwrap();
+        assert_eq!(w.get_ref(), &[0xab, 0xcd]);
+
+        w.write(128, u128::MAX).unwrap();
+        w.write_bool(false).unwrap();
+        w.write(0, 1u8).unwrap();
+        w.write(3, 0xffu8).unwrap();
+
+        assert_eq!(w.bits_written(), 148);
+
+        let bytes = w.finish(Padding::Ones).unwrap();
+
+        assert_eq!(bytes.len(), 19);
+        assert_eq!(&bytes[..2], &[0xab, 0xcd]);
+        assert!(bytes[2..18].iter().all(|&b| b == 0xff));
+        assert_eq!(bytes[18], 0b0111_1111);
+    }
+
+    /// Fails the write that would complete the `fail_at`-th byte.
+    struct FlakySink {
+        bytes: Vec<u8>,
+        fail_at: usize,
+    }
+
+    impl ByteSink for FlakySink {
+        type Error = Error;
+
+        fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+            if self.bytes.len() + bytes.len() == self.fail_at {
+                self.fail_at = 0;
+
+                return Err(Error::EndOfStream {
+                    pos: 8 * self.bytes.len() as u64,
+                });
+            }
+
+            self.bytes.extend_from_slice(bytes);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn failed_write_is_retryable() {
+        let mut w = BitStreamWriter::new(FlakySink {
+            bytes: Vec::new(),
+            fail_at: 2,
+        });
+
+        w.write(8, 0xaau8).unwrap();
+        w.write(4, 0xbu8).unwrap();
+        assert!(w.write(4, 0xbu8).is_err());
+        assert_eq!(w.bits_written(), 12);
+
+        w.write(4, 0xbu8).unwrap();
+        w.write(8, 0xccu8).unwrap();
+
+        assert_eq!(w.bits_written(), 24);
+        assert_eq!(w.get_ref().bytes, [0xaa, 0xbb, 0xcc]);
+    }
+}
@@ -0,0 +1,41 @@
+//! Helpers shared by the tests of the variable-length codes.
+
+use core::fmt::Debug;
+
+use crate::{Reader, Uint, UniversalCode, Writer};
+
+/// Returns a reader over exactly the bits written by `w`.
+pub(crate) fn read_back<T: Uint>(w: Writer<T>) -> Reader<T> {
+    let len = w.bits_written();
+
+    Reader::with_len(w.finish(), len)
+}
+
+/// Checks that each value is written as the given binary code, and that the
+/// code reads back as the value.
+pub(crate) fn check_vectors<V: Copy + PartialEq + Debug>(
+    vectors: &[(V, &str)],
+    write: impl Fn(Writer<u128>, V) -> Writer<u128>,
+    read: impl Fn(&mut Reader<u128>) -> V,
+) {
+    for &(value, code) in vectors {
+        let w = write(Writer::new(), value);
+
+        assert_eq!(usize::from(w.bits_written()), code.len(), "{value:?}");
+        assert_eq!(w.finish(), u128::from_str_radix(code, 2).unwrap());
+
+        let mut r = read_back(w);
+
+        assert_eq!(read(&mut r), value);
+        assert!(r.is_exhausted());
+    }
+}
+
+/// `check_vectors` for a `UniversalCode`.
+pub(crate) fn check_code<C: UniversalCode>(code: C, vectors: &[(u64, &str)]) {
+    check_vectors(
+        vectors,
+        |w, v| w.write_code(&code, v),
+        |r| r.read_code(&code),
+    );
+}
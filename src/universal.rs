@@ -0,0 +1,362 @@
+//! Universal codes for unbounded integers: unary and the Elias gamma, delta
+//! and omega codes. The Elias codes only represent values of at least 1.
+
+use crate::{bit_size, error, n_bit_mask, BitOrder, Error, Reader, Uint, Writer};
+
+/// A variable-length code for `u64` values. Codecs are plain values, so code
+/// that takes a `&impl UniversalCode` can be run with any of them.
+pub trait UniversalCode {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        w: Writer<T, O>,
+        value: u64,
+    ) -> Result<Writer<T, O>, Error>;
+
+    /// May stop partway through the code on error; `Reader::try_read_code`
+    /// rewinds to its start instead.
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error>;
+}
+
+/// `value` one bits followed by a zero bit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Unary;
+
+/// `floor(log2(value))` zero bits followed by `value` in binary.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EliasGamma;
+
+/// The gamma code of the bit length of `value`, followed by `value` in binary
+/// without its leading one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EliasDelta;
+
+/// `value` in binary, preceded recursively by the binary of its bit length
+/// minus one, and terminated by a zero bit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EliasOmega;
+
+#[inline]
+fn bit_len(value: u64) -> u8 {
+    (u64::BITS - value.leading_zeros()) as u8
+}
+
+fn check_positive<T: Uint, O: BitOrder>(w: &Writer<T, O>, value: u64) -> Result<(), Error> {
+    if value == 0 {
+        return Err(Error::InvalidCode {
+            pos: w.len,
+            size: bit_size::<T>(),
+        });
+    }
+
+    Ok(())
+}
+
+impl UniversalCode for Unary {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        mut w: Writer<T, O>,
+        mut value: u64,
+    ) -> Result<Writer<T, O>, Error> {
+        let chunk = u64::from(bit_size::<T>().min(u16::from(u8::MAX)));
+
+        while value > 0 {
+            let count = value.min(chunk) as u8;
+
+            w.push(count, n_bit_mask(count))?;
+            value -= u64::from(count);
+        }
+
+        w.try_write_bool(false)
+    }
+
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error> {
+        let mut value = 0;
+
+        while r.try_read_bool()? {
+            value += 1;
+        }
+
+        Ok(value)
+    }
+}
+
+impl UniversalCode for EliasGamma {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        w: Writer<T, O>,
+        value: u64,
+    ) -> Result<Writer<T, O>, Error> {
+        check_positive(&w, value)?;
+
+        w.try_write_exp_golomb(0, value - 1)
+    }
+
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error> {
+        let pos = r.pos;
+
+        r.try_read_exp_golomb(0)?
+            .checked_add(1)
+            .ok_or_else(|| r.invalid_code(pos))
+    }
+}
+
+impl UniversalCode for EliasDelta {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        w: Writer<T, O>,
+        value: u64,
+    ) -> Result<Writer<T, O>, Error> {
+        check_positive(&w, value)?;
+
+        let len = bit_len(value);
+        let w = EliasGamma.encode(w, u64::from(len))?;
+
+        w.try_write_u128(len - 1, u128::from(value ^ 1 << (len - 1)))
+    }
+
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error> {
+        let pos = r.pos;
+        let len = EliasGamma.decode(r)?;
+
+        if len > u64::from(u64::BITS) {
+            return Err(r.invalid_code(pos));
+        }
+
+        let rest = r.try_read_u128(len as u8 - 1)?;
+
+        Ok((1 << (len - 1) | rest) as u64)
+    }
+}
+
+impl UniversalCode for EliasOmega {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        mut w: Writer<T, O>,
+        mut value: u64,
+    ) -> Result<Writer<T, O>, Error> {
+        check_positive(&w, value)?;
+
+        // Groups are produced last to first; a u64 needs at most 6 of them.
+        let mut groups = [0; 8];
+        let mut n = 0;
+
+        while value > 1 {
+            groups[n] = value;
+            n += 1;
+            value = u64::from(bit_len(value) - 1);
+        }
+
+        for &group in groups[..n].iter().rev() {
+            w = w.try_write_u128(bit_len(group), u128::from(group))?;
+        }
+
+        w.try_write_bool(false)
+    }
+
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error> {
+        let pos = r.pos;
+        let mut value = 1;
+
+        while r.try_read_bool()? {
+            if value >= u64::from(u64::BITS) {
+                return Err(r.invalid_code(pos));
+            }
+
+            value = (1 << value | r.try_read_u128(value as u8)?) as u64;
+        }
+
+        Ok(value)
+    }
+}
+
+impl<T: Uint, O: BitOrder> Writer<T, O> {
+    #[track_caller]
+    pub fn write_code<C: UniversalCode>(self, code: &C, value: u64) -> Self {
+        error::unwrap(self.try_write_code(code, value))
+    }
+
+    pub fn try_write_code<C: UniversalCode>(self, code: &C, value: u64) -> Result<Self, Error> {
+        code.encode(self, value)
+    }
+
+    #[track_caller]
+    pub fn write_codes<C: UniversalCode>(self, code: &C, values: &[u64]) -> Self {
+        error::unwrap(self.try_write_codes(code, values))
+    }
+
+    pub fn try_write_codes<C: UniversalCode>(
+        self,
+        code: &C,
+        values: &[u64],
+    ) -> Result<Self, Error> {
+        values
+            .iter()
+            .try_fold(self, |w, &value| code.encode(w, value))
+    }
+}
+
+impl<T: Uint, O: BitOrder> Reader<T, O> {
+    #[track_caller]
+    pub fn read_code<C: UniversalCode>(&mut self, code: &C) -> u64 {
+        error::unwrap(self.try_read_code(code))
+    }
+
+    /// On error, the reader stays at the start of the code.
+    pub fn try_read_code<C: UniversalCode>(&mut self, code: &C) -> Result<u64, Error> {
+        self.rewind_on_error(|r| code.decode(r))
+    }
+
+    /// Fills `values` with consecutive codes.
+    #[track_caller]
+    pub fn read_codes<C: UniversalCode>(&mut self, code: &C, values: &mut [u64]) {
+        error::unwrap(self.try_read_codes(code, values))
+    }
+
+    pub fn try_read_codes<C: UniversalCode>(
+        &mut self,
+        code: &C,
+        values: &mut [u64],
+    ) -> Result<(), Error> {
+        for value in values.iter_mut() {
+            *value = self.try_read_code(code)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{check_code, read_back};
+    use crate::{ExpGolomb, Limbs};
+
+    #[test]
+    fn vectors() {
+        check_code(
+            Unary,
+            &[(0, "0"), (1, "10"), (3, "1110"), (9, "1111111110")],
+        );
+        check_code(
+            EliasGamma,
+            &[
+                (1, "1"),
+                (2, "010"),
+                (3, "011"),
+                (4, "00100"),
+                (9, "0001001"),
+            ],
+        );
+        check_code(
+            EliasDelta,
+            &[
+                (1, "1"),
+                (2, "0100"),
+                (3, "0101"),
+                (4, "01100"),
+                (7, "01111"),
+                (8, "00100000"),
+                (17, "001010001"),
+            ],
+        );
+        check_code(
+            EliasOmega,
+            &[
+                (1, "0"),
+                (2, "100"),
+                (3, "110"),
+                (4, "101000"),
+                (7, "101110"),
+                (8, "1110000"),
+                (16, "10100100000"),
+                (100, "1011011001000"),
+            ],
+        );
+    }
+
+    fn round_trip<C: UniversalCode>(code: C, values: &[u64]) {
+        let mut w = Writer::<Limbs<4>>::new();
+
+        for &value in values {
+            w = w.write_code(&code, value);
+        }
+
+        let mut r = read_back(w);
+
+        for &value in values {
+            assert_eq!(r.read_code(&code), value);
+        }
+
+        assert!(r.is_exhausted());
+    }
+
+    #[test]
+    fn swap_codecs() {
+        let values = [1, 5, 1 << 40, u64::MAX];
+
+        round_trip(EliasGamma, &values[..3]);
+        round_trip(EliasGamma, &values[3..]);
+        round_trip(EliasDelta, &values);
+        round_trip(EliasOmega, &values);
+        round_trip(Unary, &[0, 200, 3]);
+        round_trip(ExpGolomb::new(0), &values[..3]);
+        round_trip(ExpGolomb::new(20), &values);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Writer::<u32>::new()
+                .write(3, 0u8)
+                .try_write_code(&EliasOmega, 0)
+                .err(),
+            Some(Error::InvalidCode { pos: 3, size: 32 })
+        );
+        assert_eq!(
+            Writer::<u8>::new().try_write_code(&Unary, 8).err(),
+            Some(Error::OutOfBits {
+                width: 1,
+                pos: 8,
+                size: 8
+            })
+        );
+
+        // Runs of ones are split to fit the word, not the 255-bit width limit.
+        assert_eq!(
+            Writer::<u32>::new().try_write_code(&Unary, 40).err(),
+            Some(Error::OutOfBits {
+                width: 8,
+                pos: 32,
+                size: 32
+            })
+        );
+
+        // Bit length 65 in the delta prefix.
+        let mut r = read_back(Writer::<u128>::new().write_code(&EliasGamma, 65));
+
+        assert_eq!(
+            r.try_read_code(&EliasDelta),
+            Err(Error::InvalidCode { pos: 0, size: 13 })
+        );
+        assert_eq!(r.position(), 0);
+
+        let mut r = Reader::<u16>::new(0);
+
+        assert_eq!(
+            r.try_read_code(&EliasGamma),
+            Err(Error::OutOfBits {
+                width: 1,
+                pos: 16,
+                size: 16
+            })
+        );
+        assert_eq!(r.position(), 0);
+
+        let mut r = read_back(Writer::<u16>::new().write(3, 0b101u8));
+        let mut values = [0; 2];
+
+        assert!(r.try_read_codes(&Unary, &mut values).is_err());
+        assert_eq!(values[0], 1);
+        assert_eq!(r.position(), 2);
+    }
+}
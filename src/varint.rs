@@ -0,0 +1,367 @@
+//! Byte-oriented variable-length integers: LEB128 and the QUIC varint of
+//! RFC 9000, section 16. Each byte is written most significant bit first, so
+//! codes on byte boundaries of an `Msb0` word match their wire format.
+//!
+//! Decoding rejects overlong encodings with `Error::NonCanonical`, except with
+//! `QuicVarint::LENIENT`.
+
+use crate::{bit_size, error, BitOrder, Error, Reader, Uint, UniversalCode, Writer};
+
+/// Unsigned LEB128: groups of 7 bits, least significant first, each in a byte
+/// whose top bit is set when more bytes follow.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Leb128;
+
+/// QUIC varint: a 2-bit length prefix selecting 1, 2, 4 or 8 bytes, followed
+/// by the value in the remaining 6, 14, 30 or 62 bits. Values are always
+/// written in the shortest length.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct QuicVarint {
+    lenient: bool,
+}
+
+impl QuicVarint {
+    pub const MAX: u64 = (1 << 62) - 1;
+
+    /// Rejects longer encodings than needed, as required for frame types.
+    pub const STRICT: Self = QuicVarint { lenient: false };
+
+    /// Accepts any encoding length, as RFC 9000 allows for all other fields.
+    pub const LENIENT: Self = QuicVarint { lenient: true };
+}
+
+const MAX_LEB128_BYTES: u8 = 10;
+
+fn non_canonical<T: Uint, O: BitOrder>(r: &Reader<T, O>, pos: u16) -> Error {
+    Error::NonCanonical { pos, size: r.len }
+}
+
+/// Reads LEB128 bytes into `value`, returning the number of bytes read and the
+/// last two of them.
+fn read_leb128<T: Uint, O: BitOrder>(
+    r: &mut Reader<T, O>,
+    value: &mut u64,
+) -> Result<(u8, u8, u8), Error> {
+    let pos = r.pos;
+    let (mut n, mut prev) = (0, 0);
+
+    loop {
+        let byte = r.try_read_u128(8)? as u8;
+
+        if n == MAX_LEB128_BYTES {
+            return Err(r.invalid_code(pos));
+        }
+
+        *value |= u64::from(byte & 0x7f) << (7 * n);
+        n += 1;
+
+        if byte & 0x80 == 0 {
+            return Ok((n, prev, byte));
+        }
+
+        prev = byte;
+    }
+}
+
+impl UniversalCode for Leb128 {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        mut w: Writer<T, O>,
+        mut value: u64,
+    ) -> Result<Writer<T, O>, Error> {
+        loop {
+            let byte = (value & 0x7f) as u8;
+
+            value >>= 7;
+
+            if value == 0 {
+                return w.try_write_u128(8, u128::from(byte));
+            }
+
+            w = w.try_write_u128(8, u128::from(byte | 0x80))?;
+        }
+    }
+
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error> {
+        let pos = r.pos;
+        let mut value = 0;
+        let (n, _, last) = read_leb128(r, &mut value)?;
+
+        if n == MAX_LEB128_BYTES && last > 1 {
+            return Err(r.invalid_code(pos));
+        }
+
+        if n > 1 && last == 0 {
+            return Err(non_canonical(r, pos));
+        }
+
+        Ok(value)
+    }
+}
+
+impl UniversalCode for QuicVarint {
+    fn encode<T: Uint, O: BitOrder>(
+        &self,
+        w: Writer<T, O>,
+        value: u64,
+    ) -> Result<Writer<T, O>, Error> {
+        let prefix: u8 = match value {
+            0..0x40 => 0,
+            0x40..0x4000 => 1,
+            0x4000..0x4000_0000 => 2,
+            0x4000_0000..=Self::MAX => 3,
+            _ => {
+                return Err(Error::InvalidCode {
+                    pos: w.len,
+                    size: bit_size::<T>(),
+                })
+            }
+        };
+        let width = 8 << prefix;
+
+        w.try_write_u128(width, u128::from(prefix) << (width - 2) | u128::from(value))
+    }
+
+    fn decode<T: Uint, O: BitOrder>(&self, r: &mut Reader<T, O>) -> Result<u64, Error> {
+        let pos = r.pos;
+        let prefix = r.try_read_u128(2)? as u8;
+        let value = r.try_read_u128((8 << prefix) - 2)? as u64;
+
+        // The value must not fit the next shorter length.
+        if !self.lenient && prefix > 0 && value >> ((8 << (prefix - 1)) - 2) == 0 {
+            return Err(non_canonical(r, pos));
+        }
+
+        Ok(value)
+    }
+}
+
+impl<T: Uint, O: BitOrder> Writer<T, O> {
+    #[track_caller]
+    pub fn write_signed_leb128(self, value: i64) -> Self {
+        error::unwrap(self.try_write_signed_leb128(value))
+    }
+
+    /// Writes signed LEB128, where the last byte sign-extends through bit 6.
+    pub fn try_write_signed_leb128(mut self, mut value: i64) -> Result<Self, Error> {
+        loop {
+            let byte = (value & 0x7f) as u8;
+
+            value >>= 7;
+
+            if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+                return self.try_write_u128(8, u128::from(byte));
+            }
+
+            self = self.try_write_u128(8, u128::from(byte | 0x80))?;
+        }
+    }
+}
+
+impl<T: Uint, O: BitOrder> Reader<T, O> {
+    #[track_caller]
+    pub fn read_signed_leb128(&mut self) -> i64 {
+        error::unwrap(self.try_read_signed_leb128())
+    }
+
+    /// On error, the reader stays at the start of the code.
+    pub fn try_read_signed_leb128(&mut self) -> Result<i64, Error> {
+        self.rewind_on_error(|r| {
+            let pos = r.pos;
+            let mut value = 0;
+            let (n, prev, last) = read_leb128(r, &mut value)?;
+            let shift = 7 * u32::from(n);
+
+            // The tenth byte holds bit 63, and its other bits must repeat it.
+            if n == MAX_LEB128_BYTES && last != 0 && last != 0x7f {
+                return Err(r.invalid_code(pos));
+            }
+
+            // A last byte that only repeats the sign of the previous one is
+            // redundant.
+            if n > 1 && ((last == 0 && prev & 0x40 == 0) || (last == 0x7f && prev & 0x40 != 0)) {
+                return Err(non_canonical(r, pos));
+            }
+
+            if shift < u64::BITS && last & 0x40 != 0 {
+                value |= u64::MAX << shift;
+            }
+
+            Ok(value as i64)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Endian, Limbs};
+
+    fn encode(write: impl FnOnce(Writer<u128>) -> Writer<u128>) -> ([u8; 16], usize) {
+        let w = write(Writer::new());
+        let bytes = w.finish_bytes(Endian::Big);
+        let mut buf = [0; 16];
+
+        buf[..bytes.len()].copy_from_slice(&bytes);
+
+        (buf, bytes.len())
+    }
+
+    fn reader(bytes: &[u8]) -> Reader<u128> {
+        Reader::from_bytes(bytes, Endian::Big, 8 * bytes.len() as u16)
+    }
+
+    #[test]
+    fn leb128_vectors() {
+        let vectors: &[(u64, &[u8])] = &[
+            (0, &[0x00]),
+            (127, &[0x7f]),
+            (128, &[0x80, 0x01]),
+            (624_485, &[0xe5, 0x8e, 0x26]),
+            (
+                u64::MAX,
+                &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+            ),
+        ];
+
+        for &(value, bytes) in vectors {
+            let (buf, len) = encode(|w| w.write_code(&Leb128, value));
+
+            assert_eq!(&buf[..len], bytes);
+            assert_eq!(reader(bytes).read_code(&Leb128), value);
+        }
+    }
+
+    #[test]
+    fn signed_leb128_vectors() {
+        let vectors: &[(i64, &[u8])] = &[
+            (0, &[0x00]),
+            (-1, &[0x7f]),
+            (63, &[0x3f]),
+            (64, &[0xc0, 0x00]),
+            (-64, &[0x40]),
+            (-65, &[0xbf, 0x7f]),
+            (-123_456, &[0xc0, 0xbb, 0x78]),
+            (
+                i64::MIN,
+                &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f],
+            ),
+            (
+                i64::MAX,
+                &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00],
+            ),
+        ];
+
+        for &(value, bytes) in vectors {
+            let (buf, len) = encode(|w| w.write_signed_leb128(value));
+
+            assert_eq!(&buf[..len], bytes);
+            assert_eq!(reader(bytes).read_signed_leb128(), value);
+        }
+    }
+
+    #[test]
+    fn quic_vectors() {
+        let vectors: &[(u64, &[u8])] = &[
+            (37, &[0x25]),
+            (15_293, &[0x7b, 0xbd]),
+            (494_878_333, &[0x9d, 0x7f, 0x3e, 0x7d]),
+            (
+                151_288_809_941_952_652,
+                &[0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c],
+            ),
+            (QuicVarint::MAX, &[0xff; 8]),
+        ];
+
+        for &(value, bytes) in vectors {
+            let (buf, len) = encode(|w| w.write_code(&QuicVarint::STRICT, value));
+
+            assert_eq!(&buf[..len], bytes);
+            assert_eq!(reader(bytes).read_code(&QuicVarint::STRICT), value);
+        }
+    }
+
+    #[test]
+    fn non_canonical() {
+        let overlong = Error::NonCanonical { pos: 0, size: 16 };
+
+        assert_eq!(
+            reader(&[0x80, 0x00]).try_read_code(&Leb128).err(),
+            Some(overlong)
+        );
+        assert_eq!(
+            reader(&[0xff, 0x7f]).try_read_signed_leb128().err(),
+            Some(overlong)
+        );
+        assert_eq!(
+            reader(&[0x80, 0x00]).try_read_signed_leb128().err(),
+            Some(overlong)
+        );
+        assert_eq!(
+            reader(&[0x40, 0x25])
+                .try_read_code(&QuicVarint::STRICT)
+                .err(),
+            Some(overlong)
+        );
+        assert_eq!(
+            reader(&[0x80, 0x00, 0x3f, 0xff]).try_read_code(&QuicVarint::STRICT),
+            Err(Error::NonCanonical { pos: 0, size: 32 })
+        );
+
+        let mut r = reader(&[0xff, 0x7f]);
+
+        assert!(r.try_read_signed_leb128().is_err());
+        assert_eq!(r.position(), 0);
+        assert!(r.try_read_code(&Leb128).is_ok());
+
+        let mut r = reader(&[0x80, 0x80]);
+
+        assert!(r.try_read_code(&Leb128).is_err());
+        assert_eq!(r.position(), 0);
+
+        // Same lengths, shortest form.
+        assert_eq!(reader(&[0xc0, 0x00]).read_signed_leb128(), 64);
+        assert_eq!(reader(&[0x80, 0x7f]).read_signed_leb128(), -128);
+        assert_eq!(reader(&[0x40, 0x40]).read_code(&QuicVarint::STRICT), 64);
+
+        // RFC 9000 allows longer encodings outside frame types.
+        assert_eq!(reader(&[0x40, 0x25]).read_code(&QuicVarint::LENIENT), 37);
+        assert_eq!(
+            reader(&[0x80, 0x00, 0x3f, 0xff]).read_code(&QuicVarint::LENIENT),
+            0x3fff
+        );
+    }
+
+    #[test]
+    fn invalid_codes() {
+        let mut bytes = [0xff; 11];
+
+        bytes[10] = 0x01;
+
+        assert_eq!(
+            Reader::<Limbs<2>>::from_bytes(&bytes, Endian::Big, 88).try_read_code(&Leb128),
+            Err(Error::InvalidCode { pos: 0, size: 88 })
+        );
+
+        bytes[9] = 0x02;
+
+        assert_eq!(
+            Reader::<Limbs<2>>::from_bytes(&bytes[..10], Endian::Big, 80).try_read_code(&Leb128),
+            Err(Error::InvalidCode { pos: 0, size: 80 })
+        );
+
+        bytes[9] = 0x01;
+
+        assert_eq!(
+            Reader::<Limbs<2>>::from_bytes(&bytes[..10], Endian::Big, 80).try_read_signed_leb128(),
+            Err(Error::InvalidCode { pos: 0, size: 80 })
+        );
+        assert_eq!(
+            Writer::<u64>::new()
+                .try_write_code(&QuicVarint::STRICT, QuicVarint::MAX + 1)
+                .err(),
+            Some(Error::InvalidCode { pos: 0, size: 64 })
+        );
+        assert_eq!(reader(&[0x80, 0x01]).try_read_code(&Leb128).err(), None);
+    }
+}
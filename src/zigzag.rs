@@ -0,0 +1,119 @@
+use crate::{error, BitOrder, Error, Reader, Signed, Uint, UniversalCode, Writer};
+
+/// Maps signed values to unsigned ones of the same width so that values close
+/// to zero stay small: 0, -1, 1, -2, 2, ... become 0, 1, 2, 3, 4, ...
+#[inline]
+pub fn zigzag_encode<S: Signed>(value: S) -> S::Unsigned {
+    value.to_zigzag()
+}
+
+#[inline]
+pub fn zigzag_decode<S: Signed>(bits: S::Unsigned) -> S {
+    S::from_zigzag(bits)
+}
+
+impl<T: Uint, O: BitOrder> Writer<T, O> {
+    #[track_caller]
+    pub fn write_zigzag<C, S>(self, code: &C, value: S) -> Self
+    where
+        C: UniversalCode,
+        S: Signed,
+        u64: From<S::Unsigned>,
+    {
+        error::unwrap(self.try_write_zigzag(code, value))
+    }
+
+    /// Writes the ZigZag mapping of `value` with `code`.
+    pub fn try_write_zigzag<C, S>(self, code: &C, value: S) -> Result<Self, Error>
+    where
+        C: UniversalCode,
+        S: Signed,
+        u64: From<S::Unsigned>,
+    {
+        code.encode(self, zigzag_encode(value).into())
+    }
+}
+
+impl<T: Uint, O: BitOrder> Reader<T, O> {
+    #[track_caller]
+    pub fn read_zigzag<C, S>(&mut self, code: &C) -> S
+    where
+        C: UniversalCode,
+        S: Signed,
+        S::Unsigned: TryFrom<u64>,
+    {
+        error::unwrap(self.try_read_zigzag(code))
+    }
+
+    /// Reads a value written by `try_write_zigzag`. On error, the reader
+    /// stays at the start of the code.
+    pub fn try_read_zigzag<C, S>(&mut self, code: &C) -> Result<S, Error>
+    where
+        C: UniversalCode,
+        S: Signed,
+        S::Unsigned: TryFrom<u64>,
+    {
+        self.rewind_on_error(|r| {
+            let pos = r.pos;
+            let bits = code.decode(r)?;
+
+            S::Unsigned::try_from(bits)
+                .map(zigzag_decode)
+                .map_err(|_| r.invalid_code(pos))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::read_back;
+    use crate::{EliasGamma, Leb128, Limbs, Rice};
+
+    #[test]
+    fn mapping() {
+        let values = [0i32, -1, 1, -2, 2, i32::MAX, i32::MIN];
+        let mapped = [0u32, 1, 2, 3, 4, u32::MAX - 1, u32::MAX];
+
+        for (value, bits) in values.into_iter().zip(mapped) {
+            assert_eq!(zigzag_encode(value), bits);
+            assert_eq!(zigzag_decode::<i32>(bits), value);
+        }
+
+        assert_eq!(zigzag_encode(-64i8), 127);
+        assert_eq!(zigzag_encode(i128::MIN), u128::MAX);
+        assert_eq!(zigzag_decode::<i64>(u64::MAX - 1), i64::MAX);
+        assert_eq!(zigzag_decode::<isize>(5), -3);
+    }
+
+    #[test]
+    fn with_codes() {
+        let deltas = [0i16, -3, 12, -700, 1];
+        let mut w = Writer::<Limbs<4>>::new();
+
+        for delta in deltas {
+            w = w.write_zigzag(&Rice::new(6), delta);
+        }
+
+        let mut r = read_back(w.write_zigzag(&Leb128, i64::MIN));
+
+        for delta in deltas {
+            assert_eq!(r.read_zigzag::<_, i16>(&Rice::new(6)), delta);
+        }
+
+        assert_eq!(r.read_zigzag::<_, i64>(&Leb128), i64::MIN);
+        assert!(r.is_exhausted());
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut r = read_back(Writer::<u32>::new().write_code(&EliasGamma, 257));
+
+        assert_eq!(
+            r.try_read_zigzag::<_, i8>(&EliasGamma),
+            Err(Error::InvalidCode { pos: 0, size: 17 })
+        );
+        assert_eq!(r.position(), 0);
+        assert_eq!(r.read_zigzag::<_, i16>(&EliasGamma), -129);
+    }
+}
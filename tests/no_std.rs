@@ -0,0 +1,71 @@
+//! Exercises the core API the way firmware would use it: no allocator, a
+//! fixed buffer as the byte sink. Run with `cargo test --no-default-features`.
+
+#![no_std]
+
+use uint_bits::{BitStreamReader, BitStreamWriter, ByteSink, Error, Padding, Reader, Writer};
+
+struct FixedSink<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> ByteSink for FixedSink<N> {
+    type Error = Error;
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let end = self.len + bytes.len();
+
+        if end > N {
+            return Err(Error::EndOfStream { pos: 8 * N as u64 });
+        }
+
+        self.buf[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn sensor_frame() {
+    let frame = Writer::<u32>::new()
+        .write(4, 0x3u8)
+        .write_signed(12, -250i16)
+        .write_bool(true)
+        .finish_left_aligned();
+
+    let mut r = Reader::<u32>::new(frame);
+
+    assert_eq!(r.read_next(4), 0x3);
+    assert_eq!(r.read_signed::<i16>(12), -250);
+    assert!(r.read_bool());
+}
+
+#[test]
+fn fixed_buffer_stream() {
+    let mut w = BitStreamWriter::new(FixedSink::<4> {
+        buf: [0; 4],
+        len: 0,
+    });
+
+    w.write(12, 0xabcu16).unwrap();
+    w.write(13, 0x1fffu16).unwrap();
+
+    let sink = w.finish(Padding::Zeros).unwrap();
+
+    assert_eq!(sink.len, 4);
+
+    let mut r = BitStreamReader::new(&sink.buf[..]);
+
+    assert_eq!(r.read_next(12), Ok(0xabc));
+    assert_eq!(r.read_next(13), Ok(0x1fff));
+    assert_eq!(r.read_next(8), Err(Error::EndOfStream { pos: 32 }));
+
+    let mut w = BitStreamWriter::new(FixedSink::<1> {
+        buf: [0; 1],
+        len: 0,
+    });
+
+    assert!(w.write(16, 0u16).is_err());
+}
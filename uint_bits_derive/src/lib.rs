@@ -0,0 +1,406 @@
+//! `#[derive(BitPack)]` for `uint_bits`.
+//!
+//! Field attributes:
+//!
+//! * `#[bits(N)]` packs a primitive field into `N` bits.
+//! * no attribute packs the field with its own `BitPack` implementation.
+//! * `#[bits(skip)]` leaves the field out; it is `Default::default()` on unpack.
+//! * `#[bits(default = EXPR)]` leaves the field out; it is `EXPR` on unpack.
+//!
+//! A width larger than the field type fails to compile once the type is
+//! packed, since such a field could never be read back:
+//!
+//! ```compile_fail,E0080
+//! use uint_bits::BitPack;
+//!
+//! #[derive(BitPack)]
+//! struct Wide {
+//!     #[bits(9)]
+//!     x: u8,
+//! }
+//!
+//! let _: Result<u16, _> = Wide { x: 1 }.pack();
+//! ```
+//!
+//! Enums require a discriminant width on the enum itself, e.g. `#[bits(3)]`.
+//! The discriminant is followed by the variant fields, and shorter variants are
+//! zero-padded to the size of the largest one.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Expr, Fields, LitInt, Member,
+    Token, Type, WherePredicate,
+};
+
+#[proc_macro_derive(BitPack, attributes(bits))]
+pub fn derive_bit_pack(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Arg {
+    Width(LitInt),
+    Skip,
+    Default(Expr),
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitInt) {
+            return input.parse().map(Arg::Width);
+        }
+
+        let ident: syn::Ident = input.parse()?;
+
+        if ident == "skip" {
+            Ok(Arg::Skip)
+        } else if ident == "default" {
+            input.parse::<Token![=]>()?;
+            input.parse().map(Arg::Default)
+        } else {
+            Err(syn::Error::new(
+                ident.span(),
+                "expected a width, `skip` or `default = ...`",
+            ))
+        }
+    }
+}
+
+fn parse_args(attrs: &[Attribute]) -> syn::Result<Vec<Arg>> {
+    let mut args = Vec::new();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("bits")) {
+        args.extend(attr.parse_args_with(Punctuated::<Arg, Token![,]>::parse_terminated)?);
+    }
+
+    Ok(args)
+}
+
+enum Kind {
+    Nested,
+    Width(LitInt),
+    Skip(Option<Expr>),
+}
+
+struct Field {
+    member: Member,
+    ty: Type,
+    kind: Kind,
+}
+
+impl Field {
+    fn parse_all(fields: &Fields) -> syn::Result<Vec<Field>> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let member = match &f.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(i.into()),
+                };
+
+                let mut width = None;
+                let mut skip = false;
+                let mut default = None;
+
+                for arg in parse_args(&f.attrs)? {
+                    match arg {
+                        Arg::Width(lit) => width = Some(lit),
+                        Arg::Skip => skip = true,
+                        Arg::Default(expr) => default = Some(expr),
+                    }
+                }
+
+                let kind = match (width, skip || default.is_some()) {
+                    (Some(lit), true) => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "a skipped field cannot have a width",
+                        ))
+                    }
+                    (Some(lit), false) => {
+                        lit.base10_parse::<u8>()?;
+                        Kind::Width(lit)
+                    }
+                    (None, true) => Kind::Skip(default),
+                    (None, false) => Kind::Nested,
+                };
+
+                Ok(Field {
+                    member,
+                    ty: f.ty.clone(),
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    fn bits(&self) -> TokenStream {
+        let ty = &self.ty;
+
+        match &self.kind {
+            Kind::Nested => quote!(<#ty as ::uint_bits::BitPack<__T>>::BITS),
+            Kind::Width(width) => quote!((#width as u16)),
+            Kind::Skip(_) => quote!(0u16),
+        }
+    }
+
+    fn predicate(&self) -> Option<WherePredicate> {
+        let ty = &self.ty;
+
+        match &self.kind {
+            Kind::Nested => Some(parse_quote!(#ty: ::uint_bits::BitPack<__T>)),
+            Kind::Width(_) => Some(parse_quote!(
+                #ty: ::core::convert::Into<__T> + ::uint_bits::FromBits<__T>
+            )),
+            Kind::Skip(_) => None,
+        }
+    }
+
+    /// Rejects a `#[bits(N)]` width that the field type cannot be read back
+    /// from, like `bitfields!` does.
+    fn check_width(&self, width: &LitInt) -> TokenStream {
+        let member = &self.member;
+        let ty = &self.ty;
+        // The message is a format string, so braces in the type are escaped.
+        let message = format!(
+            "field `{}` is wider than `{}`",
+            quote!(#member),
+            quote!(#ty).to_string().replace(' ', ""),
+        )
+        .replace('{', "{{")
+        .replace('}', "}}");
+
+        quote! {
+            const {
+                assert!(
+                    #width as u16 <= <#ty as ::uint_bits::FromBits<__T>>::BITS,
+                    #message
+                )
+            }
+        }
+    }
+
+    fn write(&self, value: TokenStream) -> TokenStream {
+        match &self.kind {
+            Kind::Nested => quote! {
+                let w = ::uint_bits::BitPack::<__T>::write_bits(&#value, w)?;
+            },
+            Kind::Width(width) => {
+                let check = self.check_width(width);
+
+                quote! {
+                    #check
+                    let w = w.try_write_checked(#width, #value)?;
+                }
+            }
+            Kind::Skip(_) => quote!(),
+        }
+    }
+
+    fn read(&self) -> TokenStream {
+        let member = &self.member;
+        let ty = &self.ty;
+
+        let value = match &self.kind {
+            Kind::Nested => quote!(<#ty as ::uint_bits::BitPack<__T>>::read_bits(r)?),
+            Kind::Width(width) => {
+                let check = self.check_width(width);
+
+                quote!({
+                    #check
+                    r.try_read_as::<#ty>(#width)?
+                })
+            }
+            Kind::Skip(Some(default)) => quote!(#default),
+            Kind::Skip(None) => quote!(::core::default::Default::default()),
+        };
+
+        quote!(#member: #value)
+    }
+}
+
+fn sum_bits(fields: &[Field]) -> TokenStream {
+    let bits = fields.iter().map(Field::bits);
+
+    quote!((0u16 #(+ #bits)*))
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let tag_width = parse_args(&input.attrs)?
+        .into_iter()
+        .map(|arg| match arg {
+            Arg::Width(lit) => lit.base10_parse::<u8>().map(|_| lit),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "only a discriminant width is allowed on the type",
+            )),
+        })
+        .next()
+        .transpose()?;
+
+    let mut predicates = Vec::new();
+
+    let (bits, write, read, checks) = match &input.data {
+        Data::Struct(data) => {
+            if let Some(lit) = tag_width {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "a discriminant width only applies to enums",
+                ));
+            }
+
+            let fields = Field::parse_all(&data.fields)?;
+            let writes = fields.iter().map(|f| {
+                let member = &f.member;
+
+                f.write(quote!(self.#member))
+            });
+            let reads = fields.iter().map(Field::read);
+
+            predicates.extend(fields.iter().filter_map(Field::predicate));
+
+            (
+                sum_bits(&fields),
+                quote! {
+                    #(#writes)*
+                    Ok(w)
+                },
+                quote!(Ok(Self { #(#reads),* })),
+                quote!(),
+            )
+        }
+        Data::Enum(data) => {
+            let tag_width = tag_width.ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &input.ident,
+                    "enums require a discriminant width, e.g. `#[bits(3)]`",
+                )
+            })?;
+
+            let mut discriminant = quote!(0u128);
+            let mut variant_bits = Vec::new();
+            let mut write_arms = Vec::new();
+            let mut read_arms = Vec::new();
+            let mut checks = Vec::new();
+
+            for (i, variant) in data.variants.iter().enumerate() {
+                if let Some((_, expr)) = &variant.discriminant {
+                    discriminant = quote!((#expr) as u128);
+                } else if i > 0 {
+                    discriminant = quote!((#discriminant + 1));
+                }
+
+                let ident = &variant.ident;
+                let fields = Field::parse_all(&variant.fields)?;
+                let bits = sum_bits(&fields);
+                let packed: Vec<_> = fields
+                    .iter()
+                    .filter(|f| !matches!(f.kind, Kind::Skip(_)))
+                    .enumerate()
+                    .map(|(i, f)| (f, format_ident!("__f{}", i)))
+                    .collect();
+                let members = packed.iter().map(|(f, _)| &f.member);
+                let bindings = packed.iter().map(|(_, b)| b);
+                let writes = packed.iter().map(|(f, binding)| f.write(quote!(*#binding)));
+                let reads = fields.iter().map(Field::read);
+                let message =
+                    format!("discriminant of `{ident}` does not fit into {tag_width} bits");
+
+                write_arms.push(quote! {
+                    Self::#ident { #(#members: #bindings,)* .. } => {
+                        let w = ::uint_bits::__private::write_tag(w, #tag_width, #discriminant)?;
+                        #(#writes)*
+                        ::uint_bits::__private::pad(w, padded - #bits)
+                    }
+                });
+                read_arms.push(quote! {
+                    if tag == #discriminant {
+                        let v = Self::#ident { #(#reads),* };
+
+                        ::uint_bits::__private::skip(r, padded - #bits)?;
+
+                        return Ok(v);
+                    }
+                });
+                checks.push(quote! {
+                    assert!(::uint_bits::__private::fits(#discriminant, #tag_width), #message);
+                });
+
+                predicates.extend(fields.iter().filter_map(Field::predicate));
+                variant_bits.push(bits);
+            }
+
+            let padded = variant_bits.iter().fold(
+                quote!(0u16),
+                |max, bits| quote!(::uint_bits::__private::max(#max, #bits)),
+            );
+
+            (
+                quote!((#tag_width as u16 + #padded)),
+                quote! {
+                    let padded: u16 = #padded;
+
+                    match self {
+                        #(#write_arms)*
+                    }
+                },
+                quote! {
+                    let padded: u16 = #padded;
+                    let tag = ::uint_bits::__private::read_tag(r, #tag_width)?;
+
+                    #(#read_arms)*
+
+                    Err(::uint_bits::__private::unknown_tag(r, #tag_width))
+                },
+                quote! {
+                    const _: () = {
+                        #(#checks)*
+                    };
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`BitPack` cannot be derived for unions",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+
+    generics.params.push(parse_quote!(__T: ::uint_bits::Uint));
+    generics.make_where_clause().predicates.extend(predicates);
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #checks
+
+        impl #impl_generics ::uint_bits::BitPack<__T> for #ident #ty_generics #where_clause {
+            const BITS: u16 = #bits;
+
+            fn write_bits<__O: ::uint_bits::BitOrder>(
+                &self,
+                w: ::uint_bits::Writer<__T, __O>,
+            ) -> ::core::result::Result<::uint_bits::Writer<__T, __O>, ::uint_bits::Error> {
+                #write
+            }
+
+            fn read_bits<__O: ::uint_bits::BitOrder>(
+                r: &mut ::uint_bits::Reader<__T, __O>,
+            ) -> ::core::result::Result<Self, ::uint_bits::Error> {
+                #read
+            }
+        }
+    })
+}
@@ -0,0 +1,108 @@
+use uint_bits::{BitPack, Error, Lsb0, Reader, Writer};
+
+#[derive(BitPack, Debug, Clone, Copy, PartialEq, Eq)]
+#[bits(2)]
+enum Kind {
+    Data,
+    Ack = 2,
+    Nack,
+}
+
+#[derive(BitPack, Debug, PartialEq, Eq)]
+struct Header {
+    #[bits(5)]
+    channel: u8,
+    kind: Kind,
+    urgent: bool,
+    #[bits(skip)]
+    cached: Option<u32>,
+    #[bits(default = 3)]
+    version: u8,
+    #[bits(12)]
+    length: u16,
+}
+
+#[derive(BitPack, Debug, PartialEq, Eq)]
+struct Pair(#[bits(4)] u8, #[bits(4)] u8);
+
+#[derive(BitPack, Debug, PartialEq, Eq)]
+#[bits(1)]
+enum Payload {
+    Short(#[bits(3)] u8),
+    Long {
+        pair: Pair,
+        #[bits(8)]
+        extra: u16,
+    },
+}
+
+#[test]
+fn struct_round_trip() {
+    let hdr = Header {
+        channel: 17,
+        kind: Kind::Nack,
+        urgent: true,
+        cached: Some(1),
+        version: 0,
+        length: 1500,
+    };
+
+    assert_eq!(<Header as BitPack<u32>>::BITS, 20);
+
+    let bits: u32 = hdr.pack().unwrap();
+    let mut r = Reader::<u32>::with_len(bits, 20);
+
+    assert_eq!(r.read_next(5), 17);
+    assert_eq!(r.read_next(2), 3);
+    assert!(r.read_bool());
+    assert_eq!(r.read_next(12), 1500);
+
+    assert_eq!(
+        Header::unpack(bits),
+        Ok(Header {
+            cached: None,
+            version: 3,
+            ..hdr
+        })
+    );
+}
+
+#[test]
+fn enum_round_trip() {
+    assert_eq!(<Payload as BitPack<u16>>::BITS, 17);
+    assert_eq!(<Payload as BitPack<u32>>::BITS, 17);
+
+    let short = Payload::Short(5);
+    let long = Payload::Long {
+        pair: Pair(1, 2),
+        extra: 0xff,
+    };
+
+    assert_eq!(short.pack(), Ok(5u32 << 13));
+    assert_eq!(long.pack(), Ok(1u32 << 16 | 1 << 12 | 2 << 8 | 0xff));
+    assert_eq!(Payload::unpack(5u32 << 13), Ok(short));
+    assert_eq!(Payload::unpack(0x112ffu32), Ok(long));
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        <Kind as BitPack<u8>>::unpack(1),
+        Err(Error::InvalidDiscriminant {
+            width: 2,
+            pos: 0,
+            size: 2
+        })
+    );
+    assert!(BitPack::<u16>::pack(&Pair(16, 0)).is_err());
+}
+
+#[test]
+fn nested_in_writer() {
+    let w = Writer::<u64, _>::with_order(Lsb0).write(3, 7u8);
+    let w = Kind::Ack.write_bits(w).unwrap();
+    let mut r = Reader::<u64, _>::with_order(w.finish(), Lsb0);
+
+    assert_eq!(r.read_next(3), 7);
+    assert_eq!(Kind::read_bits(&mut r), Ok(Kind::Ack));
+}